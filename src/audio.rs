@@ -0,0 +1,27 @@
+use std::hash::Hasher;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum MusicCommand {
+    Play {
+        music: String,
+        is_loop: bool,
+    },
+    /// Fade current music out while the new one fades in
+    Crossfade {
+        music: String,
+        is_loop: bool,
+        /// seconds
+        duration: f32,
+    },
+    Stop,
+    #[default]
+    None,
+}
+
+impl std::hash::Hash for MusicCommand {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ron::to_string(self).unwrap().hash(state)
+    }
+}
@@ -15,8 +15,8 @@ fn main() {
     let nps_monologue = Uuid::new_v4();
     let phrase_jump_test = Uuid::new_v4();
 
-    let aerugo = Aerugo {
-        steps: vec![
+    let aerugo = Aerugo::new(
+        vec![
             Step::default(),
             // region simple texts test
             Step {
@@ -25,6 +25,7 @@ fn main() {
                 inner: Steps::Text {
                     author: str!(""),
                     texts: str!("Story flow"),
+                    voice: None,
                 },
             },
             Step {
@@ -33,6 +34,7 @@ fn main() {
                 inner: Steps::Text {
                     author: str!("??? / <?>"),
                     texts: str!("Mysterious stranger"),
+                    voice: None,
                 },
             },
             Step {
@@ -41,6 +43,7 @@ fn main() {
                 inner: Steps::Text {
                     author: str!("NPS name"),
                     texts: str!("NPS monologue"),
+                    voice: None,
                 },
             },
             // endregion
@@ -51,11 +54,19 @@ fn main() {
                 name: str!("phrase jump test"),
                 inner: Steps::Phrase {
                     phrases: vec![
-                        (str!("story_flow"), str!("Back to story flow")),
-                        (str!("mysterious_stranger"), str!("Back to mysterious stranger")),
-                        (str!("nps_monologue"), str!("Back to nps monologue")),
-                        (str!("pass"), str!("Pass")),
-                    ]
+                        PhraseOption::new("story_flow", "Back to story flow"),
+                        PhraseOption::new("mysterious_stranger", "Back to mysterious stranger"),
+                        PhraseOption {
+                            condition: Some(Condition::Not(Box::new(Condition::Check {
+                                step: phrase_jump_test,
+                                val: str!("nps_monologue"),
+                            }))),
+                            disabled: Some(str!("Heard it just now")),
+                            ..PhraseOption::new("nps_monologue", "Back to nps monologue")
+                        },
+                        PhraseOption::new("pass", "Pass"),
+                    ],
+                    timeout: None,
                 },
             },
             Step {
@@ -92,7 +103,7 @@ fn main() {
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("Try see narrator sprite_step") },
+                inner: Steps::Text { author: str!(""), texts: str!("Try see narrator sprite_step"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -105,7 +116,7 @@ fn main() {
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!("Sprite narrator"), texts: str!("Hi!") },
+                inner: Steps::Text { author: str!("Sprite narrator"), texts: str!("Hi!"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -117,7 +128,7 @@ fn main() {
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("Hide them") },
+                inner: Steps::Text { author: str!(""), texts: str!("Hide them"), voice: None },
             },
             // endregion
 
@@ -125,7 +136,7 @@ fn main() {
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: "".to_string(), texts: str!("Sprite test") },
+                inner: Steps::Text { author: "".to_string(), texts: str!("Sprite test"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -134,22 +145,29 @@ fn main() {
                     sprite: str!("textures/char/female_sprite.png"),
                     name: str!("FadeChan"),
                     position: -0.2,
+                    transform: Default::default(),
+                    duration: None,
+                    easing: Easing::Linear,
                 }),
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: "".to_string(), texts: str!("Fade in") },
+                inner: Steps::Text { author: "".to_string(), texts: str!("Fade in"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Sprite(SpriteCommand::FadeOut { name: str!("FadeChan") }),
+                inner: Steps::Sprite(SpriteCommand::FadeOut {
+                    name: str!("FadeChan"),
+                    duration: None,
+                    easing: Easing::Linear,
+                }),
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: "".to_string(), texts: str!("Fade out") },
+                inner: Steps::Text { author: "".to_string(), texts: str!("Fade out"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -158,22 +176,29 @@ fn main() {
                     sprite: str!("textures/char/female_sprite.png"),
                     name: str!("LeftChan"),
                     position: -0.35,
+                    transform: Default::default(),
+                    duration: None,
+                    easing: Easing::Linear,
                 }),
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: "".to_string(), texts: str!("Left in") },
+                inner: Steps::Text { author: "".to_string(), texts: str!("Left in"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Sprite(SpriteCommand::LeftOut { name: str!("LeftChan") }),
+                inner: Steps::Sprite(SpriteCommand::LeftOut {
+                    name: str!("LeftChan"),
+                    duration: None,
+                    easing: Easing::Linear,
+                }),
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: "".to_string(), texts: str!("Left out") },
+                inner: Steps::Text { author: "".to_string(), texts: str!("Left out"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -182,22 +207,29 @@ fn main() {
                     sprite: str!("textures/char/female_sprite.png"),
                     name: str!("RightChan"),
                     position: 0.35,
+                    transform: Default::default(),
+                    duration: None,
+                    easing: Easing::Linear,
                 }),
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: "".to_string(), texts: str!("Right in") },
+                inner: Steps::Text { author: "".to_string(), texts: str!("Right in"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Sprite(SpriteCommand::RightOut { name: str!("RightChan") }),
+                inner: Steps::Sprite(SpriteCommand::RightOut {
+                    name: str!("RightChan"),
+                    duration: None,
+                    easing: Easing::Linear,
+                }),
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: "".to_string(), texts: str!("Right out") },
+                inner: Steps::Text { author: "".to_string(), texts: str!("Right out"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -206,32 +238,64 @@ fn main() {
                     sprite: str!("textures/char/female_sprite.png"),
                     name: str!("MoveChan"),
                     position: 0.7,
+                    transform: Default::default(),
+                    duration: None,
+                    easing: Easing::Linear,
                 }),
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: "".to_string(), texts: str!("Move prepare") },
+                inner: Steps::Text { author: "".to_string(), texts: str!("Move prepare"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Sprite(SpriteCommand::Move { name: str!("MoveChan"), position: -0.7 }),
+                inner: Steps::Sprite(SpriteCommand::Move {
+                    name: str!("MoveChan"),
+                    position: -0.7,
+                    duration: Some(1.5),
+                    easing: Easing::Bounce,
+                }),
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: "".to_string(), texts: str!("Wo-o-o-o") },
+                inner: Steps::Text { author: "".to_string(), texts: str!("Wo-{speed=0.3x}o-o-o"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Sprite(SpriteCommand::FadeOut { name: str!("MoveChan") }),
+                inner: Steps::Sprite(SpriteCommand::Transform {
+                    name: str!("MoveChan"),
+                    transform: SpriteTransform {
+                        scale: 0.8,
+                        flip: true,
+                        tint: [0.5, 0.5, 0.5, 1.0],
+                        ..Default::default()
+                    },
+                    duration: None,
+                    easing: Easing::EaseInOut,
+                }),
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: "".to_string(), texts: str!("Leave...") },
+                inner: Steps::Text { author: "".to_string(), texts: str!("In the shadow"), voice: None },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Sprite(SpriteCommand::FadeOut {
+                    name: str!("MoveChan"),
+                    duration: None,
+                    easing: Easing::Linear,
+                }),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: "".to_string(), texts: str!("Leave..."), voice: None },
             },
             // endregion
 
@@ -239,7 +303,7 @@ fn main() {
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("Try set background") },
+                inner: Steps::Text { author: str!(""), texts: str!("Try set background"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -247,12 +311,223 @@ fn main() {
                 inner: Steps::Background(BackgroundCommand::Change {
                     new: str!("textures/background/pexels-francesco-ungaro.jpg"),
                     animation: None,
+                    duration: None,
+                    easing: Easing::Linear,
+                }),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("Looks good"), voice: None },
+            },
+            // endregion
+
+            // region screen test
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Screen(ScreenCommand::Zoom {
+                    x: 0.2,
+                    y: 0.1,
+                    scale: 2.0,
+                    duration: Some(1.5),
+                    easing: Easing::EaseInOut,
+                }),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("[ruby=zoom]Closer[/ruby]..."), voice: None },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Screen(ScreenCommand::Reset { duration: Some(1.0), easing: Easing::Linear }),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Screen(ScreenCommand::Vignette {
+                    strength: 0.6,
+                    duration: Some(1.0),
+                    easing: Easing::Linear,
+                }),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("Something is [shake]wrong[/shake]"), voice: None },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Screen(ScreenCommand::Flash { color: ScreenCommand::RED, duration: 0.3 }),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("Ouch!"), voice: None },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Screen(ScreenCommand::FadeTo {
+                    color: ScreenCommand::BLACK,
+                    duration: Some(1.5),
+                    easing: Easing::EaseIn,
+                }),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("[i]Darkness[/i]"), voice: None },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Screen(ScreenCommand::FadeFrom { duration: Some(1.0), easing: Easing::Linear }),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Screen(ScreenCommand::Vignette {
+                    strength: 0.0,
+                    duration: Some(1.0),
+                    easing: Easing::Linear,
+                }),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("Back to light"), voice: None },
+            },
+            // endregion
+
+            // region particles test
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Particles(ParticlesCommand::Start {
+                    kind: ParticleKind::Rain,
+                    intensity: 1.0,
+                    wind: 0.3,
+                    sprite: None,
                 }),
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("Looks good") },
+                inner: Steps::Text { author: str!(""), texts: str!("Rain is coming"), voice: None },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Particles(ParticlesCommand::Stop { kind: Some(ParticleKind::Rain) }),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Particles(ParticlesCommand::Start {
+                    kind: ParticleKind::Petals,
+                    intensity: 1.5,
+                    wind: 0.5,
+                    sprite: None,
+                }),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("[color=#e07090]Spring[/color] is [wave]here[/wave]"), voice: None },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Particles(ParticlesCommand::Stop { kind: None }),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("Clear sky"), voice: None },
+            },
+            // endregion
+
+            // region input test
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Input {
+                    prompt: str!("What is your name?"),
+                    var: str!("mc"),
+                    default: str!("Alex"),
+                    max_len: Some(16),
+                },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!("{mc}"), texts: str!("My name is {mc}."), voice: None },
+            },
+            // endregion
+
+            // region timed phrase test
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Phrase {
+                    phrases: vec![
+                        PhraseOption::new("run", "Run"),
+                        PhraseOption::new("hide", "Hide"),
+                    ],
+                    timeout: Some(PhraseTimeout { seconds: 5.0, default: str!("hide") }),
+                },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("Whatever you did, it worked"), voice: None },
+            },
+            // endregion
+
+            // region nvl test
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::TextMode(TextModeCommand::Nvl),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("The night was long, and the road was longer."), voice: None },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("Every lantern we passed {w=0.5}went dark behind us."), voice: None },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!("Narrator"), texts: str!("We should have stayed in town."), voice: None },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::TextMode(TextModeCommand::ClearPage),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("By dawn the [i]village[/i] was gone."), voice: None },
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::TextMode(TextModeCommand::Adv),
+            },
+            Step {
+                id: Uuid::new_v4(),
+                name: "".to_string(),
+                inner: Steps::Text { author: str!(""), texts: str!("Back to the text box"), voice: None },
             },
             // endregion
 
@@ -260,7 +535,7 @@ fn main() {
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("Try to do simple scene test") },
+                inner: Steps::Text { author: str!(""), texts: str!("Try to do simple scene test"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -270,7 +545,7 @@ fn main() {
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("Looks good") },
+                inner: Steps::Text { author: str!(""), texts: str!("Looks good"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -280,12 +555,12 @@ fn main() {
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("Remove them better") },
+                inner: Steps::Text { author: str!(""), texts: str!("Remove them better"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("Try animate scene") },
+                inner: Steps::Text { author: str!(""), texts: str!("Try animate scene"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -296,12 +571,18 @@ fn main() {
                     tile: (610, 343),
                     columns: 20,
                     rows: 1,
+                    fps: None,
+                    easing: Easing::Linear,
+                    frames: None,
+                    ping_pong: false,
+                    wait: false,
+                    from: None,
                 }),
             },
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("Bounce, bounce, bounce...") },
+                inner: Steps::Text { author: str!(""), texts: str!("Bounce,{w=0.4} bounce,{p} bounce..."), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -311,7 +592,7 @@ fn main() {
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("STOP!1!") },
+                inner: Steps::Text { author: str!(""), texts: str!("STOP!1!"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -321,7 +602,7 @@ fn main() {
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("Resume") },
+                inner: Steps::Text { author: str!(""), texts: str!("Resume"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -331,7 +612,7 @@ fn main() {
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("Stop") },
+                inner: Steps::Text { author: str!(""), texts: str!("Stop{w=1}{nw}"), voice: None },
             },
             Step {
                 id: Uuid::new_v4(),
@@ -341,17 +622,17 @@ fn main() {
             Step {
                 id: Uuid::new_v4(),
                 name: "".to_string(),
-                inner: Steps::Text { author: str!(""), texts: str!("All done!:)") },
+                inner: Steps::Text { author: str!(""), texts: str!("All done!:)"), voice: None },
             },
             // endregion
 
             Step {  // End
                 id: Uuid::new_v4(),
                 name: str!("End"),
-                inner: Steps::Text { author: str!("Narrator"), texts: str!("The End!") },
+                inner: Steps::Text { author: str!("Narrator"), texts: str!("The End!"), voice: None },
             },
         ],
-    };
+    );
 
     let data = ron::ser::to_string_pretty(&aerugo, Default::default()).unwrap();
     let save_path = std::path::Path::new(SCENARIO_PATH);
@@ -46,25 +46,32 @@ pub fn edit_international(
                             };
                             match (&mut ru.inner, &mut en.inner) {
                                 (Steps::Text {
-                                    author: ru_a, texts: ru_t
+                                    author: ru_a, texts: ru_t, ..
                                 }, Steps::Text {
-                                    author: en_a, texts: en_t
+                                    author: en_a, texts: en_t, ..
                                 }) => {
                                     ui.vertical(|ui| {
                                         ui.horizontal(|ui| {
                                             ui.label("RU");
                                             ui.text_edit_singleline(ru_a);
                                             ui.text_edit_multiline(ru_t);
+                                            // markup is not read by player
+                                            ui.label(format!(
+                                                "{} chars", plain_text(ru_t).chars().count()
+                                            ));
                                         });
                                         ui.horizontal(|ui| {
                                             ui.label("EN");
                                             ui.text_edit_singleline(en_a);
                                             ui.text_edit_multiline(en_t);
+                                            ui.label(format!(
+                                                "{} chars", plain_text(en_t).chars().count()
+                                            ));
                                         });
                                     });
                                 }
-                                (Steps::Phrase { phrases: ru },
-                                    Steps::Phrase { phrases: en }) => {
+                                (Steps::Phrase { phrases: ru, .. },
+                                    Steps::Phrase { phrases: en, .. }) => {
                                     ui.vertical(|ui| {
                                         ui.horizontal(|ui| {
                                             ui.label("RU");
@@ -74,13 +81,31 @@ pub fn edit_international(
                                         for (ru, en) in ru.iter_mut()
                                             .zip(en.iter_mut()) {
                                             ui.horizontal(|ui| {
-                                                ui.text_edit_singleline(&mut ru.1);
+                                                ui.text_edit_singleline(&mut ru.verbose);
                                                 ui.separator();
-                                                ui.text_edit_singleline(&mut en.1);
+                                                ui.text_edit_singleline(&mut en.verbose);
                                             });
                                         }
                                     });
                                 }
+                                (Steps::Input {
+                                    prompt: ru_p, default: ru_d, ..
+                                }, Steps::Input {
+                                    prompt: en_p, default: en_d, ..
+                                }) => {
+                                    ui.vertical(|ui| {
+                                        ui.horizontal(|ui| {
+                                            ui.label("RU");
+                                            ui.text_edit_singleline(ru_p);
+                                            ui.text_edit_singleline(ru_d);
+                                        });
+                                        ui.horizontal(|ui| {
+                                            ui.label("EN");
+                                            ui.text_edit_singleline(en_p);
+                                            ui.text_edit_singleline(en_d);
+                                        });
+                                    });
+                                }
                                 (_, _) => {}
                             }
                         });
@@ -8,11 +8,22 @@ pub enum LightInner {
     Text,
     Jump,
     Phrase,
+    Call,
+    Return,
     ImageSelect,
+    Input,
+    SetVar,
+    ModifyVar,
     SpriteNarrator,
     Sprite,
+    Character,
     Background,
     Scene,
+    Screen,
+    Particles,
+    TextMode,
+    Music,
+    Sound,
     #[default]
     None,
 }
@@ -23,11 +34,22 @@ impl From<Steps> for LightInner {
             Steps::Text { .. } => { LightInner::Text }
             Steps::Jump { .. } => { LightInner::Jump }
             Steps::Phrase { .. } => { LightInner::Phrase }
+            Steps::Call { .. } => { LightInner::Call }
+            Steps::Return => { LightInner::Return }
             Steps::ImageSelect { .. } => { LightInner::ImageSelect }
+            Steps::Input { .. } => { LightInner::Input }
+            Steps::SetVar { .. } => { LightInner::SetVar }
+            Steps::ModifyVar { .. } => { LightInner::ModifyVar }
             Steps::SpriteNarrator { .. } => { LightInner::SpriteNarrator }
             Steps::Sprite(_) => { LightInner::Sprite }
+            Steps::Character(_) => { LightInner::Character }
             Steps::Background(_) => { LightInner::Background }
             Steps::Scene(_) => { LightInner::Scene }
+            Steps::Screen(_) => { LightInner::Screen }
+            Steps::Particles(_) => { LightInner::Particles }
+            Steps::TextMode(_) => { LightInner::TextMode }
+            Steps::Music(_) => { LightInner::Music }
+            Steps::Sound { .. } => { LightInner::Sound }
             Steps::None => { LightInner::None }
         }
     }
@@ -37,29 +59,67 @@ impl Into<Steps> for LightInner {
     fn into(self) -> Steps {
         match self {
             LightInner::Text => {
-                Steps::Text { author: "".to_string(), texts: "".to_string() }
+                Steps::Text { author: "".to_string(), texts: "".to_string(), voice: None }
             }
             LightInner::Jump => {
                 Steps::Jump { condition: None, target: Default::default() }
             }
             LightInner::Phrase => {
-                Steps::Phrase { phrases: vec![] }
+                Steps::Phrase { phrases: vec![], timeout: None }
+            }
+            LightInner::Call => {
+                Steps::Call { target: Default::default() }
+            }
+            LightInner::Return => {
+                Steps::Return
             }
             LightInner::ImageSelect => {
                 Steps::ImageSelect { background: "".to_string(), options: Default::default() }
             }
+            LightInner::Input => {
+                Steps::Input {
+                    prompt: "".to_string(),
+                    var: "".to_string(),
+                    default: "".to_string(),
+                    max_len: None,
+                }
+            }
+            LightInner::SetVar => {
+                Steps::SetVar { var: "".to_string(), value: Default::default() }
+            }
+            LightInner::ModifyVar => {
+                Steps::ModifyVar { var: "".to_string(), operation: Default::default() }
+            }
             LightInner::SpriteNarrator => {
                 Steps::SpriteNarrator(NarratorCommand::None)
             }
             LightInner::Sprite => {
                 Steps::Sprite(SpriteCommand::None)
             }
+            LightInner::Character => {
+                Steps::Character(CharacterCommand::None)
+            }
             LightInner::Background => {
                 Steps::Background(BackgroundCommand::None)
             }
             LightInner::Scene => {
                 Steps::Scene(SceneCommand::None)
             }
+            LightInner::Screen => {
+                Steps::Screen(ScreenCommand::None)
+            }
+            LightInner::Particles => {
+                Steps::Particles(ParticlesCommand::None)
+            }
+            LightInner::TextMode => {
+                Steps::TextMode(TextModeCommand::None)
+            }
+            LightInner::Music => {
+                Steps::Music(MusicCommand::None)
+            }
+            LightInner::Sound => {
+                Steps::Sound { sound: "".to_string() }
+            }
             LightInner::None => {
                 Steps::None
             }
@@ -121,6 +181,7 @@ pub enum SpriteLight {
     RightIn,
     RightOut,
     Move,
+    Transform,
 }
 
 impl From<SpriteCommand> for SpriteLight {
@@ -136,6 +197,7 @@ impl From<SpriteCommand> for SpriteLight {
             SpriteCommand::RightIn { .. } => { SpriteLight::RightIn }
             SpriteCommand::RightOut { .. } => { SpriteLight::RightOut }
             SpriteCommand::Move { .. } => { SpriteLight::Move }
+            SpriteCommand::Transform { .. } => { SpriteLight::Transform }
         }
     }
 }
@@ -147,31 +209,127 @@ impl Into<SpriteCommand> for SpriteLight {
                 SpriteCommand::None
             }
             SpriteLight::Set => {
-                SpriteCommand::Set { name: "".to_string(), sprite: "".to_string(), position: 0.0 }
+                SpriteCommand::Set {
+                    name: "".to_string(),
+                    sprite: "".to_string(),
+                    position: 0.0,
+                    transform: Default::default(),
+                }
             }
             SpriteLight::Remove => {
                 SpriteCommand::Remove { name: "".to_string() }
             }
             SpriteLight::FadeIn => {
-                SpriteCommand::FadeIn { sprite: "".to_string(), name: "".to_string(), position: 0.0 }
+                SpriteCommand::FadeIn {
+                    sprite: "".to_string(),
+                    name: "".to_string(),
+                    position: 0.0,
+                    transform: Default::default(),
+                    duration: None,
+                    easing: Default::default(),
+                }
             }
             SpriteLight::FadeOut => {
-                SpriteCommand::FadeOut { name: "".to_string() }
+                SpriteCommand::FadeOut {
+                    name: "".to_string(),
+                    duration: None,
+                    easing: Default::default(),
+                }
             }
             SpriteLight::LeftIn => {
-                SpriteCommand::LeftIn { sprite: "".to_string(), name: "".to_string(), position: 0.0 }
+                SpriteCommand::LeftIn {
+                    sprite: "".to_string(),
+                    name: "".to_string(),
+                    position: 0.0,
+                    transform: Default::default(),
+                    duration: None,
+                    easing: Default::default(),
+                }
             }
             SpriteLight::LeftOut => {
-                SpriteCommand::LeftOut { name: "".to_string() }
+                SpriteCommand::LeftOut {
+                    name: "".to_string(),
+                    duration: None,
+                    easing: Default::default(),
+                }
             }
             SpriteLight::RightIn => {
-                SpriteCommand::RightIn { sprite: "".to_string(), name: "".to_string(), position: 0.0 }
+                SpriteCommand::RightIn {
+                    sprite: "".to_string(),
+                    name: "".to_string(),
+                    position: 0.0,
+                    transform: Default::default(),
+                    duration: None,
+                    easing: Default::default(),
+                }
             }
             SpriteLight::RightOut => {
-                SpriteCommand::RightOut { name: "".to_string() }
+                SpriteCommand::RightOut {
+                    name: "".to_string(),
+                    duration: None,
+                    easing: Default::default(),
+                }
             }
             SpriteLight::Move => {
-                SpriteCommand::Move { name: "".to_string(), position: 0.0 }
+                SpriteCommand::Move {
+                    name: "".to_string(),
+                    position: 0.0,
+                    duration: None,
+                    easing: Default::default(),
+                }
+            }
+            SpriteLight::Transform => {
+                SpriteCommand::Transform {
+                    name: "".to_string(),
+                    transform: Default::default(),
+                    duration: None,
+                    easing: Default::default(),
+                }
+            }
+        }
+    }
+}
+
+
+#[derive(EnumIter, Debug, Clone, Default, Eq, PartialEq)]
+pub enum CharacterLight {
+    #[default]
+    None,
+    Show,
+    Outfit,
+    Expression,
+}
+
+impl From<CharacterCommand> for CharacterLight {
+    fn from(cmd: CharacterCommand) -> Self {
+        match cmd {
+            CharacterCommand::Show { .. } => { CharacterLight::Show }
+            CharacterCommand::Outfit { .. } => { CharacterLight::Outfit }
+            CharacterCommand::Expression { .. } => { CharacterLight::Expression }
+            CharacterCommand::None => { CharacterLight::None }
+        }
+    }
+}
+
+impl From<CharacterLight> for CharacterCommand {
+    fn from(light: CharacterLight) -> CharacterCommand {
+        match light {
+            CharacterLight::None => { CharacterCommand::None }
+            CharacterLight::Show => {
+                CharacterCommand::Show {
+                    name: "".to_string(),
+                    outfit: None,
+                    expression: None,
+                    position: None,
+                    transform: Default::default(),
+                    duration: None,
+                }
+            }
+            CharacterLight::Outfit => {
+                CharacterCommand::Outfit { name: "".to_string(), outfit: None }
+            }
+            CharacterLight::Expression => {
+                CharacterCommand::Expression { name: "".to_string(), expression: None }
             }
         }
     }
@@ -184,6 +342,7 @@ pub enum BackgroundLight {
     None,
     Change,
     Shake,
+    ShakeWith,
 }
 
 impl From<BackgroundCommand> for BackgroundLight {
@@ -191,6 +350,7 @@ impl From<BackgroundCommand> for BackgroundLight {
         match cmd {
             BackgroundCommand::Change { .. } => { BackgroundLight::Change }
             BackgroundCommand::Shake => { BackgroundLight::Shake }
+            BackgroundCommand::ShakeWith { .. } => { BackgroundLight::ShakeWith }
             BackgroundCommand::None => { BackgroundLight::None }
         }
     }
@@ -201,9 +361,17 @@ impl Into<BackgroundCommand> for BackgroundLight {
         match self {
             BackgroundLight::None => { BackgroundCommand::None }
             BackgroundLight::Change => {
-                BackgroundCommand::Change { new: "".to_string(), animation: None }
+                BackgroundCommand::Change {
+                    new: "".to_string(),
+                    animation: None,
+                    duration: None,
+                    easing: Default::default(),
+                }
             }
             BackgroundLight::Shake => { BackgroundCommand::Shake }
+            BackgroundLight::ShakeWith => {
+                BackgroundCommand::ShakeWith { strength: 0.02, duration: 0.5 }
+            }
         }
     }
 }
@@ -248,6 +416,12 @@ impl Into<SceneCommand> for SceneLight {
                     tile: (0, 0),
                     columns: 0,
                     rows: 0,
+                    fps: None,
+                    easing: Default::default(),
+                    frames: None,
+                    ping_pong: false,
+                    wait: false,
+                    from: None,
                 }
             }
             SceneLight::Pause => { SceneCommand::Pause }
@@ -256,3 +430,208 @@ impl Into<SceneCommand> for SceneLight {
         }
     }
 }
+
+
+#[derive(EnumIter, Debug, Clone, Default, Eq, PartialEq)]
+pub enum ScreenLight {
+    #[default]
+    None,
+    Zoom,
+    Pan,
+    Reset,
+    Flash,
+    FadeTo,
+    FadeFrom,
+    Vignette,
+}
+
+impl From<ScreenCommand> for ScreenLight {
+    fn from(cmd: ScreenCommand) -> Self {
+        match cmd {
+            ScreenCommand::Zoom { .. } => { ScreenLight::Zoom }
+            ScreenCommand::Pan { .. } => { ScreenLight::Pan }
+            ScreenCommand::Reset { .. } => { ScreenLight::Reset }
+            ScreenCommand::Flash { .. } => { ScreenLight::Flash }
+            ScreenCommand::FadeTo { .. } => { ScreenLight::FadeTo }
+            ScreenCommand::FadeFrom { .. } => { ScreenLight::FadeFrom }
+            ScreenCommand::Vignette { .. } => { ScreenLight::Vignette }
+            ScreenCommand::None => { ScreenLight::None }
+        }
+    }
+}
+
+impl From<ScreenLight> for ScreenCommand {
+    fn from(light: ScreenLight) -> ScreenCommand {
+        match light {
+            ScreenLight::None => { ScreenCommand::None }
+            ScreenLight::Zoom => {
+                ScreenCommand::Zoom {
+                    x: 0.0,
+                    y: 0.0,
+                    scale: 1.5,
+                    duration: None,
+                    easing: Default::default(),
+                }
+            }
+            ScreenLight::Pan => {
+                ScreenCommand::Pan { x: 0.0, y: 0.0, duration: None, easing: Default::default() }
+            }
+            ScreenLight::Reset => {
+                ScreenCommand::Reset { duration: None, easing: Default::default() }
+            }
+            ScreenLight::Flash => {
+                ScreenCommand::Flash { color: ScreenCommand::WHITE, duration: 0.3 }
+            }
+            ScreenLight::FadeTo => {
+                ScreenCommand::FadeTo {
+                    color: ScreenCommand::BLACK,
+                    duration: None,
+                    easing: Default::default(),
+                }
+            }
+            ScreenLight::FadeFrom => {
+                ScreenCommand::FadeFrom { duration: None, easing: Default::default() }
+            }
+            ScreenLight::Vignette => {
+                ScreenCommand::Vignette {
+                    strength: 0.5,
+                    duration: None,
+                    easing: Default::default(),
+                }
+            }
+        }
+    }
+}
+
+
+#[derive(EnumIter, Debug, Clone, Default, Eq, PartialEq)]
+pub enum ParticlesLight {
+    #[default]
+    None,
+    Start,
+    Stop,
+}
+
+impl From<ParticlesCommand> for ParticlesLight {
+    fn from(cmd: ParticlesCommand) -> Self {
+        match cmd {
+            ParticlesCommand::Start { .. } => { ParticlesLight::Start }
+            ParticlesCommand::Stop { .. } => { ParticlesLight::Stop }
+            ParticlesCommand::None => { ParticlesLight::None }
+        }
+    }
+}
+
+impl From<ParticlesLight> for ParticlesCommand {
+    fn from(light: ParticlesLight) -> ParticlesCommand {
+        match light {
+            ParticlesLight::None => { ParticlesCommand::None }
+            ParticlesLight::Start => {
+                ParticlesCommand::Start {
+                    kind: Default::default(),
+                    intensity: 1.0,
+                    wind: 0.0,
+                    sprite: None,
+                }
+            }
+            ParticlesLight::Stop => { ParticlesCommand::Stop { kind: None } }
+        }
+    }
+}
+
+
+#[derive(EnumIter, Debug, Clone, Default, Eq, PartialEq)]
+pub enum MusicLight {
+    #[default]
+    None,
+    Play,
+    Crossfade,
+    Stop,
+}
+
+impl From<MusicCommand> for MusicLight {
+    fn from(cmd: MusicCommand) -> Self {
+        match cmd {
+            MusicCommand::Play { .. } => { MusicLight::Play }
+            MusicCommand::Crossfade { .. } => { MusicLight::Crossfade }
+            MusicCommand::Stop => { MusicLight::Stop }
+            MusicCommand::None => { MusicLight::None }
+        }
+    }
+}
+
+impl From<MusicLight> for MusicCommand {
+    fn from(light: MusicLight) -> MusicCommand {
+        match light {
+            MusicLight::None => { MusicCommand::None }
+            MusicLight::Play => {
+                MusicCommand::Play { music: "".to_string(), is_loop: true }
+            }
+            MusicLight::Crossfade => {
+                MusicCommand::Crossfade { music: "".to_string(), is_loop: true, duration: 1.0 }
+            }
+            MusicLight::Stop => { MusicCommand::Stop }
+        }
+    }
+}
+
+
+#[derive(EnumIter, Debug, Clone, Default, Eq, PartialEq)]
+pub enum VariableLight {
+    #[default]
+    Int,
+    Bool,
+    Str,
+}
+
+impl From<Variable> for VariableLight {
+    fn from(var: Variable) -> Self {
+        match var {
+            Variable::Int(_) => { VariableLight::Int }
+            Variable::Bool(_) => { VariableLight::Bool }
+            Variable::Str(_) => { VariableLight::Str }
+        }
+    }
+}
+
+impl From<VariableLight> for Variable {
+    fn from(light: VariableLight) -> Variable {
+        match light {
+            VariableLight::Int => { Variable::Int(0) }
+            VariableLight::Bool => { Variable::Bool(false) }
+            VariableLight::Str => { Variable::Str("".to_string()) }
+        }
+    }
+}
+
+
+#[derive(EnumIter, Debug, Clone, Default, Eq, PartialEq)]
+pub enum VarOperationLight {
+    #[default]
+    Add,
+    Sub,
+    Toggle,
+    Append,
+}
+
+impl From<VarOperation> for VarOperationLight {
+    fn from(operation: VarOperation) -> Self {
+        match operation {
+            VarOperation::Add(_) => { VarOperationLight::Add }
+            VarOperation::Sub(_) => { VarOperationLight::Sub }
+            VarOperation::Toggle => { VarOperationLight::Toggle }
+            VarOperation::Append(_) => { VarOperationLight::Append }
+        }
+    }
+}
+
+impl From<VarOperationLight> for VarOperation {
+    fn from(light: VarOperationLight) -> VarOperation {
+        match light {
+            VarOperationLight::Add => { VarOperation::Add(1) }
+            VarOperationLight::Sub => { VarOperation::Sub(1) }
+            VarOperationLight::Toggle => { VarOperation::Toggle }
+            VarOperationLight::Append => { VarOperation::Append("".to_string()) }
+        }
+    }
+}
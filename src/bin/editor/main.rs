@@ -2,8 +2,8 @@ mod light;
 mod scenario;
 mod international;
 
+use std::collections::BTreeMap;
 use std::fmt::Debug;
-use std::io::{Read, Write};
 use std::ops::{Deref, DerefMut};
 use bevy::prelude::*;
 use bevy::utils::HashSet;
@@ -15,7 +15,10 @@ use strum_macros::EnumIter;
 use aerugo::*;
 use aerugo::international::{ImanityLangs, Internationale};
 use crate::egui::Ui;
-use crate::light::{BackgroundLight, LightInner, NarratorLight, SceneLight, SpriteLight};
+use crate::light::{
+    BackgroundLight, CharacterLight, LightInner, MusicLight, NarratorLight, ParticlesLight,
+    SceneLight, ScreenLight, SpriteLight, VariableLight, VarOperationLight,
+};
 use scenario::*;
 use international::*;
 
@@ -28,37 +31,27 @@ fn main() {
         .run();
 }
 
-const SCENARIO_PATH: &str = "scenario.ron";
-
 fn setup(
     mut commands: Commands,
 )
 {
-    let aerugo = {
-        let mut file = std::fs::File::options()
-            .read(true).write(true).create(true)
-            .open(SCENARIO_PATH)
-            .unwrap();
-        let mut buff = String::new();
-        file.read_to_string(&mut buff).unwrap();
-        ron::from_str::<Aerugo>(&buff).unwrap_or_default()
+    // editing a half-loaded scenario would overwrite the broken chapters on save
+    let aerugo = match Aerugo::load("").and_then(|aerugo| { aerugo.validate().map(|_| aerugo) }) {
+        Ok(aerugo) => { aerugo }
+        Err(e) => {
+            error!("Scenario can't be edited: {e}");
+            std::process::exit(1);
+        }
     };
     // TODO: save edit state
-    let state = AerugoState::new(&aerugo);
+    let state = AerugoState::new(&aerugo).unwrap_or_default();
     commands.insert_resource(aerugo);
     commands.insert_resource(state);
     commands.insert_resource(Internationale::load());
 }
 
 fn save(aerugo: &Aerugo, internationale: &Internationale) {
-    let aerugo = ron::ser::to_string_pretty(aerugo, default())
-        .unwrap();
-    std::fs::File::options()
-        .write(true).truncate(true)
-        .open(SCENARIO_PATH)
-        .unwrap()
-        .write(aerugo.as_bytes())
-        .unwrap();
+    aerugo.dump("").unwrap();
 
     for local in &internationale.defs {
         let name = std::env::current_dir().unwrap()
@@ -127,11 +120,19 @@ pub fn horizontal_text(ui: &mut egui::Ui, label: &str, v: &mut String) {
 
 
 fn file_pick(ui: &mut Ui, target: &mut String) {
+    filtered_pick(ui, target, "image", &["png"]);
+}
+
+fn audio_pick(ui: &mut Ui, target: &mut String) {
+    filtered_pick(ui, target, "audio", &["flac", "mp3", "wav"]);
+}
+
+fn filtered_pick(ui: &mut Ui, target: &mut String, name: &str, extensions: &[&str]) {
     if ui.button("FILE").clicked() {
         let path = std::env::current_dir().unwrap().join("assets");
 
         let res = rfd::FileDialog::new()
-            .add_filter("image", &["png"])
+            .add_filter(name, extensions)
             .set_directory(&path)
             .pick_files();
         if let Some(file) = res {
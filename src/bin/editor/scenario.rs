@@ -34,6 +34,7 @@ pub fn edit_scenario(
             _ => {}
         }
     }
+    let names = StepNames::new(targets.iter().map(|(id, name)| { (id, name) }));
     egui::CentralPanel::default().show(
         egui_ctx,
         |mut ui| {
@@ -44,7 +45,14 @@ pub fn edit_scenario(
                     let mut delete = None;
                     let mut mc = None;
                     let mut sc = None;
+                    let steps_count = aerugo.steps.len();
+                    let chapters = &aerugo.chapters;
+                    let characters = &aerugo.characters;
                     for (i, step) in aerugo.steps.iter_mut().enumerate() {
+                        if let Some(chapter) = chapters.iter()
+                            .find(|c| { c.steps.first() == Some(&step.id) }) {
+                            ui.heading(&chapter.path);
+                        }
                         ui.horizontal(|ui| {
                             if ui.button("INSERT").clicked() {
                                 insert = Some(i);
@@ -65,7 +73,15 @@ pub fn edit_scenario(
                                 sc = Some((i, false));
                             }
                         });
-                        step_widget(ui, step, &targets, &narrator_names, &narrator_sprites);
+                        step_widget(
+                            ui,
+                            step,
+                            &targets,
+                            &names,
+                            characters,
+                            &narrator_names,
+                            &narrator_sprites,
+                        );
                     }
                     ui.horizontal(|ui| {
                         if ui.button("+").clicked() {
@@ -79,7 +95,7 @@ pub fn edit_scenario(
                                 })
                             ));
                             aerugo.steps.push(Step::new().with_inner(
-                                Steps::Text { author: "Ты".to_string(), texts: "".to_string() }
+                                Steps::Text { author: "Ты".to_string(), texts: "".to_string(), voice: None }
                             ));
                             aerugo.steps.push(Step::new().with_inner(
                                 Steps::SpriteNarrator(NarratorCommand::Clean)
@@ -123,6 +139,9 @@ pub fn edit_scenario(
                             step,
                         )
                     }
+                    if aerugo.steps.len() != steps_count || insert.is_some() || delete.is_some() {
+                        aerugo.reindex();
+                    }
                 },
             );
         },
@@ -133,6 +152,8 @@ fn step_widget(
     ui: &mut egui::Ui,
     step: &mut Step,
     targets: &Vec<(Uuid, String)>,
+    names: &StepNames,
+    characters: &BTreeMap<String, Character>,
     narrator_names: &Vec<String>,
     narrator_sprites: &Vec<String>,
 )
@@ -149,8 +170,9 @@ fn step_widget(
                     ui.text_edit_singleline(&mut step.name);
                 });
                 light_edit::<_, LightInner>(ui, &mut step.inner, "Type");
+                let id = step.id;
                 match &mut step.inner {
-                    Steps::Text { author, texts } => {
+                    Steps::Text { author, texts, voice } => {
                         ui.heading("Text");
                         ui.horizontal(|ui| {
                             egui::ComboBox::from_label("Author:").width(200.0)
@@ -169,6 +191,16 @@ fn step_widget(
                             ui.label("Text:");
                             ui.text_edit_multiline(texts);
                         });
+                        let mut has_voice = voice.is_some();
+                        ui.checkbox(&mut has_voice, "Voice");
+                        match (has_voice, voice.as_mut()) {
+                            (true, Some(voice)) => {
+                                horizontal_text(ui, "Voice:", voice);
+                                audio_pick(ui, voice);
+                            }
+                            (true, None) => { *voice = Some(default()); }
+                            (false, _) => { *voice = None; }
+                        }
                     }
                     Steps::Jump { condition, target } => {
                         ui.heading("Jump");
@@ -183,27 +215,122 @@ fn step_widget(
                                     );
                                 }
                             });
-                        ui.label(format!("Condition: {:?}", condition));
+                        condition_edit(ui, id, condition, names);
                     }
-                    Steps::Phrase { phrases } => {
+                    Steps::Phrase { phrases, timeout } => {
                         ui.heading("Phrase");
                         ui.horizontal(|ui| {
                             ui.label("Phrase");
                             ui.separator();
                             ui.label("Verbose");
                         });
-                        for (phrase, verbose) in phrases.iter_mut() {
+                        for (i, option) in phrases.iter_mut().enumerate() {
                             ui.horizontal(|ui| {
-                                ui.text_edit_singleline(phrase);
+                                ui.text_edit_singleline(&mut option.key);
                                 ui.separator();
-                                ui.text_edit_singleline(verbose);
+                                ui.text_edit_singleline(&mut option.verbose);
+                            });
+                            ui.indent((id, i), |ui| {
+                                condition_edit(ui, (id, i), &mut option.condition, names);
+                                if option.condition.is_none() { return; }
+                                let mut greyed = option.disabled.is_some();
+                                ui.checkbox(&mut greyed, "Greyed when false");
+                                match (greyed, option.disabled.as_mut()) {
+                                    (true, Some(reason)) => {
+                                        horizontal_text(ui, "Reason:", reason);
+                                    }
+                                    (true, None) => { option.disabled = Some(default()); }
+                                    (false, _) => { option.disabled = None; }
+                                }
                             });
                         }
                         if ui.button("+").clicked() {
                             phrases.push(default());
                         }
+                        let mut timed = timeout.is_some();
+                        ui.checkbox(&mut timed, "Timeout");
+                        match (timed, timeout.as_mut()) {
+                            (true, Some(timeout)) => {
+                                ui.horizontal(|ui| {
+                                    ui.label("Seconds:");
+                                    ui.add(egui::DragValue::new(&mut timeout.seconds).speed(0.1)
+                                        .clamp_range(0.5..=60.0));
+                                });
+                                egui::ComboBox::from_label("Default")
+                                    .selected_text(&timeout.default)
+                                    .show_ui(ui, |ui| {
+                                        for option in phrases.iter() {
+                                            ui.selectable_value(
+                                                &mut timeout.default,
+                                                option.key.clone(),
+                                                &option.key,
+                                            );
+                                        }
+                                    });
+                            }
+                            (true, None) => {
+                                *timeout = Some(PhraseTimeout {
+                                    seconds: 5.0,
+                                    default: phrases.first()
+                                        .map(|option| { option.key.clone() })
+                                        .unwrap_or_default(),
+                                });
+                            }
+                            (false, _) => { *timeout = None; }
+                        }
+                    }
+                    Steps::Call { target } => {
+                        ui.heading("Call");
+                        egui::ComboBox::from_label("Target")
+                            .selected_text(format!("{}", target))
+                            .show_ui(ui, |ui| {
+                                for (option, verbose) in targets {
+                                    ui.selectable_value(
+                                        target,
+                                        *option,
+                                        format!("{} - {}", option, verbose),
+                                    );
+                                }
+                            });
                     }
+                    Steps::Return => {}
                     Steps::ImageSelect { .. } => {}
+                    Steps::Input { prompt, var, default, max_len } => {
+                        ui.heading("Input");
+                        horizontal_text(ui, "Prompt:", prompt);
+                        horizontal_text(ui, "Var:", var);
+                        horizontal_text(ui, "Default:", default);
+                        ui.horizontal(|ui| {
+                            let mut limited = max_len.is_some();
+                            ui.checkbox(&mut limited, "Max length");
+                            match (limited, max_len.as_mut()) {
+                                (true, Some(max_len)) => {
+                                    ui.add(egui::DragValue::new(max_len).clamp_range(1..=100));
+                                }
+                                (true, None) => { *max_len = Some(16); }
+                                (false, _) => { *max_len = None; }
+                            }
+                        });
+                    }
+                    Steps::SetVar { var, value } => {
+                        ui.heading("SetVar");
+                        horizontal_text(ui, "Var:", var);
+                        variable_edit(ui, value);
+                    }
+                    Steps::ModifyVar { var, operation } => {
+                        ui.heading("ModifyVar");
+                        horizontal_text(ui, "Var:", var);
+                        light_edit::<_, VarOperationLight>(ui, operation, "Operation");
+                        match operation {
+                            VarOperation::Add(n) | VarOperation::Sub(n) => {
+                                ui.add(egui::DragValue::new(n));
+                            }
+                            VarOperation::Toggle => {}
+                            VarOperation::Append(s) => {
+                                horizontal_text(ui, "Text:", s);
+                            }
+                        }
+                    }
                     Steps::SpriteNarrator(cmd) => {
                         ui.heading("SpriteNarrator");
                         light_edit::<_, NarratorLight>(ui, cmd, "Kind");
@@ -225,58 +352,121 @@ fn step_widget(
                         light_edit::<_, SpriteLight>(ui, cmd, "Kind");
                         match cmd {
                             SpriteCommand::None => {}
-                            SpriteCommand::Set { sprite, name, position } => {
+                            SpriteCommand::Set { sprite, name, position, transform } => {
                                 horizontal_text(ui, "Sprite:", sprite);
                                 file_pick(ui, sprite);
                                 horizontal_text(ui, "Name:", name);
                                 ui.add(egui::DragValue::new(position).speed(0.1));
+                                transform_edit(ui, transform);
                             }
                             SpriteCommand::Remove { name } => {
                                 horizontal_text(ui, "Name:", name);
                             }
-                            SpriteCommand::FadeIn { sprite, name, position } => {
+                            SpriteCommand::FadeIn {
+                                sprite, name, position, transform, duration, easing
+                            } => {
                                 horizontal_text(ui, "Sprite:", sprite);
                                 file_pick(ui, sprite);
                                 horizontal_text(ui, "Name:", name);
                                 ui.add(egui::DragValue::new(position).speed(0.1));
+                                transform_edit(ui, transform);
+                                timing_edit(ui, duration, easing);
                             }
-                            SpriteCommand::FadeOut { name } => {
+                            SpriteCommand::FadeOut { name, duration, easing } => {
                                 horizontal_text(ui, "Name:", name);
+                                timing_edit(ui, duration, easing);
                             }
-                            SpriteCommand::LeftIn { sprite, name, position } => {
+                            SpriteCommand::LeftIn {
+                                sprite, name, position, transform, duration, easing
+                            } => {
                                 horizontal_text(ui, "Sprite:", sprite);
                                 file_pick(ui, sprite);
                                 horizontal_text(ui, "Name:", name);
                                 ui.add(egui::DragValue::new(position).speed(0.1));
+                                transform_edit(ui, transform);
+                                timing_edit(ui, duration, easing);
                             }
-                            SpriteCommand::LeftOut { name } => {
+                            SpriteCommand::LeftOut { name, duration, easing } => {
                                 horizontal_text(ui, "Name:", name);
+                                timing_edit(ui, duration, easing);
                             }
-                            SpriteCommand::RightIn { sprite, name, position } => {
+                            SpriteCommand::RightIn {
+                                sprite, name, position, transform, duration, easing
+                            } => {
                                 horizontal_text(ui, "Sprite:", sprite);
                                 file_pick(ui, sprite);
                                 horizontal_text(ui, "Name:", name);
                                 ui.add(egui::DragValue::new(position).speed(0.1));
+                                transform_edit(ui, transform);
+                                timing_edit(ui, duration, easing);
                             }
-                            SpriteCommand::RightOut { name } => {
+                            SpriteCommand::RightOut { name, duration, easing } => {
                                 horizontal_text(ui, "Name:", name);
+                                timing_edit(ui, duration, easing);
                             }
-                            SpriteCommand::Move { name, position } => {
+                            SpriteCommand::Move { name, position, duration, easing } => {
                                 horizontal_text(ui, "Name:", name);
                                 ui.add(egui::DragValue::new(position).speed(0.1));
+                                timing_edit(ui, duration, easing);
+                            }
+                            SpriteCommand::Transform { name, transform, duration, easing } => {
+                                horizontal_text(ui, "Name:", name);
+                                transform_edit(ui, transform);
+                                timing_edit(ui, duration, easing);
                             }
                         }
                     }
+                    Steps::Character(cmd) => {
+                        ui.heading("Character");
+                        light_edit::<_, CharacterLight>(ui, cmd, "Kind");
+                        match cmd {
+                            CharacterCommand::Show {
+                                name, outfit, expression, position, transform, duration
+                            } => {
+                                character_pick(ui, name, characters);
+                                let character = characters.get(name);
+                                layer_pick(ui, "Outfit", outfit, character.map(|c| { &c.outfits }));
+                                let expressions = character.map(|c| { &c.expressions });
+                                layer_pick(ui, "Expression", expression, expressions);
+                                optional_edit(ui, "Position:", position, 0.0);
+                                transform_edit(ui, transform);
+                                optional_edit(ui, "Fade in:", duration, 1.0);
+                            }
+                            CharacterCommand::Outfit { name, outfit } => {
+                                character_pick(ui, name, characters);
+                                let character = characters.get(name);
+                                layer_pick(ui, "Outfit", outfit, character.map(|c| { &c.outfits }));
+                            }
+                            CharacterCommand::Expression { name, expression } => {
+                                character_pick(ui, name, characters);
+                                let character = characters.get(name);
+                                let expressions = character.map(|c| { &c.expressions });
+                                layer_pick(ui, "Expression", expression, expressions);
+                            }
+                            CharacterCommand::None => {}
+                        }
+                    }
                     Steps::Background(cmd) => {
                         ui.heading("Background");
                         light_edit::<_, BackgroundLight>(ui, cmd, "Kind");
                         match cmd {
-                            BackgroundCommand::Change { new, animation } => {
+                            BackgroundCommand::Change { new, animation, duration, easing } => {
                                 horizontal_text(ui, "Name:", new);
                                 file_pick(ui, new);
                                 ui.label(format!("Animation: {:?}", animation));  // TODO
+                                timing_edit(ui, duration, easing);
                             }
                             BackgroundCommand::Shake => {}
+                            BackgroundCommand::ShakeWith { strength, duration } => {
+                                ui.horizontal(|ui| {
+                                    ui.label("Strength:");
+                                    ui.add(egui::DragValue::new(strength).speed(0.01));
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label("Duration:");
+                                    ui.add(egui::DragValue::new(duration).speed(0.1));
+                                });
+                            }
                             BackgroundCommand::None => {}
                         }
                     }
@@ -289,7 +479,10 @@ fn step_widget(
                                 file_pick(ui, name);
                             }
                             SceneCommand::Remove => {}
-                            SceneCommand::Play { name, is_loop, tile, columns, rows } => {
+                            SceneCommand::Play {
+                                name, is_loop, tile, columns, rows, fps, easing, frames, ping_pong,
+                                wait, ..
+                            } => {
                                 horizontal_text(ui, "Name:", name);
                                 file_pick(ui, name);
                                 ui.checkbox(is_loop, "Is loop");
@@ -306,6 +499,24 @@ fn step_widget(
                                     ui.label("Rows:");
                                     ui.add(egui::DragValue::new(rows));
                                 });
+                                optional_edit(ui, "FPS", fps, 24.0);
+                                easing_edit(ui, easing);
+                                ui.horizontal(|ui| {
+                                    let mut set = frames.is_some();
+                                    ui.checkbox(&mut set, "Frames");
+                                    match (set, frames.as_mut()) {
+                                        (true, Some((first, last))) => {
+                                            ui.add(egui::DragValue::new(first));
+                                            ui.add(egui::DragValue::new(last));
+                                        }
+                                        (true, None) => {
+                                            *frames = Some((0, (*columns * *rows).max(1) - 1));
+                                        }
+                                        (false, _) => { *frames = None; }
+                                    }
+                                });
+                                ui.checkbox(ping_pong, "Ping-pong");
+                                ui.add_enabled(!*is_loop, egui::Checkbox::new(wait, "Wait"));
                             }
                             SceneCommand::Pause => {}
                             SceneCommand::Resume => {}
@@ -313,6 +524,127 @@ fn step_widget(
                             SceneCommand::None => {}
                         }
                     }
+                    Steps::Screen(cmd) => {
+                        ui.heading("Screen");
+                        light_edit::<_, ScreenLight>(ui, cmd, "Kind");
+                        match cmd {
+                            ScreenCommand::Zoom { x, y, scale, duration, easing } => {
+                                point_edit(ui, x, y);
+                                ui.horizontal(|ui| {
+                                    ui.label("Scale:");
+                                    ui.add(egui::DragValue::new(scale).speed(0.01));
+                                });
+                                timing_edit(ui, duration, easing);
+                            }
+                            ScreenCommand::Pan { x, y, duration, easing } => {
+                                point_edit(ui, x, y);
+                                timing_edit(ui, duration, easing);
+                            }
+                            ScreenCommand::Reset { duration, easing } => {
+                                timing_edit(ui, duration, easing);
+                            }
+                            ScreenCommand::Flash { color, duration } => {
+                                ui.horizontal(|ui| {
+                                    ui.label("Color:");
+                                    ui.color_edit_button_rgba_unmultiplied(color);
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label("Duration:");
+                                    ui.add(egui::DragValue::new(duration).speed(0.1));
+                                });
+                            }
+                            ScreenCommand::FadeTo { color, duration, easing } => {
+                                ui.horizontal(|ui| {
+                                    ui.label("Color:");
+                                    ui.color_edit_button_rgba_unmultiplied(color);
+                                });
+                                timing_edit(ui, duration, easing);
+                            }
+                            ScreenCommand::FadeFrom { duration, easing } => {
+                                timing_edit(ui, duration, easing);
+                            }
+                            ScreenCommand::Vignette { strength, duration, easing } => {
+                                ui.horizontal(|ui| {
+                                    ui.label("Strength:");
+                                    ui.add(egui::Slider::new(strength, 0.0..=1.0));
+                                });
+                                timing_edit(ui, duration, easing);
+                            }
+                            ScreenCommand::None => {}
+                        }
+                    }
+                    Steps::Particles(cmd) => {
+                        ui.heading("Particles");
+                        light_edit::<_, ParticlesLight>(ui, cmd, "Kind");
+                        match cmd {
+                            ParticlesCommand::Start { kind, intensity, wind, sprite } => {
+                                particle_kind_pick(ui, kind);
+                                ui.horizontal(|ui| {
+                                    ui.label("Intensity:");
+                                    ui.add(egui::DragValue::new(intensity).speed(0.05)
+                                        .clamp_range(0.0..=5.0));
+                                });
+                                ui.horizontal(|ui| {
+                                    ui.label("Wind:");
+                                    ui.add(egui::DragValue::new(wind).speed(0.05));
+                                });
+                                let mut has_sprite = sprite.is_some();
+                                ui.checkbox(&mut has_sprite, "Sprite");
+                                match (has_sprite, sprite.as_mut()) {
+                                    (true, Some(sprite)) => {
+                                        horizontal_text(ui, "Sprite:", sprite);
+                                        file_pick(ui, sprite);
+                                    }
+                                    (true, None) => { *sprite = Some(default()); }
+                                    (false, _) => { *sprite = None; }
+                                }
+                            }
+                            ParticlesCommand::Stop { kind } => {
+                                let mut all = kind.is_none();
+                                ui.checkbox(&mut all, "All");
+                                match (all, kind.as_mut()) {
+                                    (false, Some(kind)) => { particle_kind_pick(ui, kind); }
+                                    (false, None) => { *kind = Some(default()); }
+                                    (true, _) => { *kind = None; }
+                                }
+                            }
+                            ParticlesCommand::None => {}
+                        }
+                    }
+                    Steps::TextMode(cmd) => {
+                        ui.heading("Text mode");
+                        text_mode_pick(ui, cmd);
+                    }
+                    Steps::Music(cmd) => {
+                        ui.heading("Music");
+                        light_edit::<_, MusicLight>(ui, cmd, "Kind");
+                        match cmd {
+                            MusicCommand::Play { music, is_loop } => {
+                                horizontal_text(ui, "Music:", music);
+                                audio_pick(ui, music);
+                                ui.checkbox(is_loop, "Is loop");
+                            }
+                            MusicCommand::Crossfade { music, is_loop, duration } => {
+                                horizontal_text(ui, "Music:", music);
+                                audio_pick(ui, music);
+                                ui.checkbox(is_loop, "Is loop");
+                                ui.horizontal(|ui| {
+                                    ui.label("Duration:");
+                                    ui.add(egui::DragValue::new(duration)
+                                        .clamp_range(0.0..=60.0)
+                                        .speed(0.1)
+                                    );
+                                });
+                            }
+                            MusicCommand::Stop => {}
+                            MusicCommand::None => {}
+                        }
+                    }
+                    Steps::Sound { sound } => {
+                        ui.heading("Sound");
+                        horizontal_text(ui, "Sound:", sound);
+                        audio_pick(ui, sound);
+                    }
                     Steps::None => {}
                 }
                 ui.label(format!("DBG: {:?}", step.inner));
@@ -321,6 +653,183 @@ fn step_widget(
 }
 
 
+/// Empty text is no condition
+fn parse_condition(text: &str, names: &StepNames) -> Result<Option<Condition>, ConditionError> {
+    if text.trim().is_empty() {
+        return Ok(None);
+    }
+    Condition::parse(text, names).map(Some)
+}
+
+fn condition_edit(
+    ui: &mut egui::Ui,
+    id: impl std::hash::Hash,
+    condition: &mut Option<Condition>,
+    names: &StepNames,
+)
+{
+    let text_id = egui::Id::new(("condition", id));
+    // keep typed text while it is invalid or still means the same condition
+    let typed = ui.data().get_temp::<String>(text_id)
+        .filter(|text| {
+            parse_condition(text, names).map_or(true, |parsed| { parsed == *condition })
+        });
+    let mut text = typed.unwrap_or_else(|| {
+        condition.as_ref().map(|c| { c.pretty(names) }).unwrap_or_default()
+    });
+    ui.horizontal(|ui| {
+        ui.label("Condition:");
+        ui.text_edit_singleline(&mut text);
+    });
+    match parse_condition(&text, names) {
+        Ok(parsed) => { *condition = parsed; }
+        Err(e) => { ui.colored_label(egui::Color32::RED, e.to_string()); }
+    }
+    ui.data().insert_temp(text_id, text);
+}
+
+fn variable_edit(ui: &mut egui::Ui, value: &mut Variable) {
+    light_edit::<_, VariableLight>(ui, value, "Value");
+    match value {
+        Variable::Int(n) => {
+            ui.add(egui::DragValue::new(n));
+        }
+        Variable::Bool(b) => {
+            ui.checkbox(b, "");
+        }
+        Variable::Str(s) => {
+            ui.text_edit_singleline(s);
+        }
+    }
+}
+
+fn transform_edit(ui: &mut egui::Ui, transform: &mut SpriteTransform) {
+    ui.horizontal(|ui| {
+        ui.label("Y:");
+        ui.add(egui::DragValue::new(&mut transform.y).speed(0.1));
+        ui.label("Scale:");
+        ui.add(egui::DragValue::new(&mut transform.scale).speed(0.05).clamp_range(0.0..=f32::MAX));
+        ui.label("Layer:");
+        ui.add(egui::DragValue::new(&mut transform.layer));
+        ui.checkbox(&mut transform.flip, "Flip");
+    });
+    ui.horizontal(|ui| {
+        ui.label("Tint:");
+        ui.color_edit_button_rgba_unmultiplied(&mut transform.tint);
+    });
+}
+
+/// Checkbox to set value at all, `default` when it gets set
+fn optional_edit(ui: &mut egui::Ui, label: &str, value: &mut Option<f32>, default: f32) {
+    ui.horizontal(|ui| {
+        let mut set = value.is_some();
+        ui.checkbox(&mut set, label);
+        match (set, value.as_mut()) {
+            (true, Some(value)) => {
+                ui.add(egui::DragValue::new(value).speed(0.1));
+            }
+            (true, None) => { *value = Some(default); }
+            (false, _) => { *value = None; }
+        }
+    });
+}
+
+fn character_pick(ui: &mut egui::Ui, name: &mut String, characters: &BTreeMap<String, Character>) {
+    egui::ComboBox::from_label("Character")
+        .selected_text(name.as_str())
+        .show_ui(ui, |ui| {
+            for character in characters.keys() {
+                ui.selectable_value(name, character.clone(), character);
+            }
+        });
+}
+
+fn layer_pick(
+    ui: &mut egui::Ui,
+    label: &str,
+    layer: &mut Option<String>,
+    layers: Option<&BTreeMap<String, String>>,
+)
+{
+    egui::ComboBox::from_label(label)
+        .selected_text(layer.as_deref().unwrap_or("None"))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(layer, None, "None");
+            for key in layers.into_iter().flat_map(|l| { l.keys() }) {
+                ui.selectable_value(layer, Some(key.clone()), key);
+            }
+        });
+}
+
+fn particle_kind_pick(ui: &mut egui::Ui, kind: &mut ParticleKind) {
+    const KINDS: [ParticleKind; 4] = [
+        ParticleKind::Rain,
+        ParticleKind::Snow,
+        ParticleKind::Dust,
+        ParticleKind::Petals,
+    ];
+    egui::ComboBox::from_label("Particle")
+        .selected_text(format!("{:?}", kind))
+        .show_ui(ui, |ui| {
+            for option in KINDS {
+                ui.selectable_value(kind, option, format!("{:?}", option));
+            }
+        });
+}
+
+fn text_mode_pick(ui: &mut egui::Ui, cmd: &mut TextModeCommand) {
+    const MODES: [TextModeCommand; 4] = [
+        TextModeCommand::None,
+        TextModeCommand::Adv,
+        TextModeCommand::Nvl,
+        TextModeCommand::ClearPage,
+    ];
+    egui::ComboBox::from_label("Mode")
+        .selected_text(format!("{:?}", cmd))
+        .show_ui(ui, |ui| {
+            for option in MODES {
+                let text = format!("{:?}", option);
+                ui.selectable_value(cmd, option, text);
+            }
+        });
+}
+
+/// Fractions of window from its center
+fn point_edit(ui: &mut egui::Ui, x: &mut f32, y: &mut f32) {
+    ui.horizontal(|ui| {
+        ui.label("Center:");
+        ui.add(egui::DragValue::new(x).speed(0.01).clamp_range(-0.5..=0.5));
+        ui.add(egui::DragValue::new(y).speed(0.01).clamp_range(-0.5..=0.5));
+    });
+}
+
+fn timing_edit(ui: &mut egui::Ui, duration: &mut Option<f32>, easing: &mut Easing) {
+    optional_edit(ui, "Duration:", duration, 1.0);
+    easing_edit(ui, easing);
+}
+
+fn easing_edit(ui: &mut egui::Ui, easing: &mut Easing) {
+    const EASINGS: [Easing; 9] = [
+        Easing::Linear,
+        Easing::EaseIn,
+        Easing::EaseOut,
+        Easing::EaseInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::Bounce,
+        Easing::Elastic,
+    ];
+    egui::ComboBox::from_label("Easing")
+        .selected_text(format!("{:?}", easing))
+        .show_ui(ui, |ui| {
+            for option in EASINGS {
+                ui.selectable_value(easing, option, format!("{:?}", option));
+            }
+        });
+}
+
+
 pub fn light_edit<O, L>(ui: &mut egui::Ui, origin: &mut O, label: &str)
     where
         O: Clone,
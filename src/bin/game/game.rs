@@ -2,6 +2,7 @@
 
 use std::collections::HashMap;
 
+use bevy::audio::AudioSink;
 use bevy::prelude::*;
 
 use aerugo::*;
@@ -21,12 +22,23 @@ impl Plugin for GamePlugin {
             .add_event::<NextStepEvent>()
             .add_event::<NewNarratorEvent>()
             .add_event::<NewSpriteEvent>()
+            .add_event::<NewCharacterEvent>()
             .add_event::<NewBackgroundEvent>()
             .add_event::<NewSceneEvent>()
+            .add_event::<NewScreenEvent>()
+            .add_event::<NewParticlesEvent>()
+            .add_event::<NewMusicEvent>()
+            .add_event::<NewSoundEvent>()
+            .init_resource::<AudioChannels>()
             .add_state(GameState::None)
+            .add_system(aerugo_failure_listener)
+            .add_system(fade_music)
             .add_system_set(
                 SystemSet::on_enter(GameState::None)
                     .with_system(hide_game)
+                    .with_system(stop_audio)
+                    .with_system(stop_background_animation)
+                    .with_system(reset_screen)
             )
             .add_system_set(
                 SystemSet::on_update(GameState::Init)
@@ -39,17 +51,27 @@ impl Plugin for GamePlugin {
             .add_system_set(
                 SystemSet::on_update(GameState::Active)
                     .with_system(open_overlay)
+                    .with_system(input_rollback)
                     .with_system(next_step_listener)
                     .with_system(step_init.after(next_step_listener))
                     .with_system(new_narrator_listener.after(step_init))
                     .with_system(new_background_listener.after(step_init))
                     .with_system(new_scene_listener.after(step_init))
-                    .with_system(new_sprite_listener.after(step_init))
+                    .with_system(new_screen_listener.after(step_init))
+                    .with_system(new_particles_listener.after(step_init))
+                    .with_system(animate_particles.after(new_particles_listener))
+                    .with_system(new_character_listener.after(step_init))
+                    .with_system(new_sprite_listener.after(new_character_listener))
+                    .with_system(new_music_listener.after(step_init))
+                    .with_system(new_sound_listener.after(step_init))
                     .with_system(animate
                         .after(new_narrator_listener)
                         .after(new_background_listener)
                         .after(new_scene_listener)
+                        .after(new_screen_listener)
                         .after(new_sprite_listener))
+                    .with_system(fit_sprites.after(animate))
+                    .with_system(sync_character_layers.after(fit_sprites))
             )
             .add_system_set(
                 SystemSet::on_enter(GameState::Paused)
@@ -82,14 +104,66 @@ pub struct NextStepEvent;
 #[derive(Debug, Resource)]
 pub struct JustInit;
 
+/// Scenario cannot be continued, shown by [`UiState::Error`](crate::ui::UiState::Error)
+#[derive(Debug, Resource)]
+pub struct AerugoFailure {
+    pub error: AerugoError,
+    /// No way back to main menu
+    pub fatal: bool,
+}
+
 pub struct NewNarratorEvent(pub NarratorCommand);
 
 pub struct NewSpriteEvent(pub SpriteCommand);
 
+pub struct NewCharacterEvent(pub CharacterCommand);
+
 pub struct NewBackgroundEvent(pub BackgroundCommand);
 
 pub struct NewSceneEvent(pub SceneCommand);
 
+pub struct NewScreenEvent(pub ScreenCommand);
+
+pub struct NewParticlesEvent(pub ParticlesCommand);
+
+pub struct NewMusicEvent(pub MusicCommand);
+
+pub struct NewSoundEvent(pub String);
+
+/// Strong handles of playing sinks, weak ones can not stop playback
+#[derive(Debug, Default, Resource)]
+pub struct AudioChannels {
+    /// (path, sink)
+    pub music: Option<(String, Handle<AudioSink>)>,
+    pub fades: Vec<AudioFade>,
+    pub voice: Option<Handle<AudioSink>>,
+}
+
+#[derive(Debug)]
+pub struct AudioFade {
+    pub sink: Handle<AudioSink>,
+    pub timer: Timer,
+    /// Otherwise fade out and stop
+    pub fade_in: bool,
+}
+
+impl AudioChannels {
+    pub fn stop_music(&mut self, sinks: &Assets<AudioSink>) {
+        let music = self.music.take().map(|(_, sink)| { sink });
+        for sink in music.into_iter().chain(self.fades.drain(..).map(|f| { f.sink })) {
+            if let Some(sink) = sinks.get(&sink) {
+                sink.stop();
+            }
+        }
+    }
+
+    pub fn stop_voice(&mut self, sinks: &Assets<AudioSink>) {
+        if let Some(sink) = self.voice.take().and_then(|sink| { sinks.get(&sink) }) {
+            sink.stop();
+        }
+    }
+}
+
 pub struct GameControlPlugin;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -98,6 +172,8 @@ pub enum GameControlState {
     TextPass,
     Text,
     Phrase,
+    ImageSelect,
+    Input,
 }
 
 impl Plugin for GameControlPlugin {
@@ -116,6 +192,14 @@ impl Plugin for GameControlPlugin {
                 SystemSet::on_update(GameControlState::Phrase)
                     .with_system(input_phrase)
             )
+            .add_system_set(
+                SystemSet::on_update(GameControlState::ImageSelect)
+                    .with_system(input_image_select)
+            )
+            .add_system_set(
+                SystemSet::on_update(GameControlState::Input)
+                    .with_system(input_text_field)
+            )
         ;
     }
 }
\ No newline at end of file
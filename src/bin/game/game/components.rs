@@ -1,5 +1,9 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 
+use aerugo::{Easing, parse_markup, ParticleKind, TextMarker, TextPiece};
+
 // region text flow
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Component)]
 pub struct TextFlowBase;
@@ -10,18 +14,158 @@ pub struct TextFlowMark;
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Component)]
 pub struct NarratorFlowMark;
 
+/// Typewriter of `Steps::Text`, stays after the end while some piece has an effect
 #[derive(Debug, Clone, Component)]
 pub struct AnimateText {
-    pub text: String,
+    pub pieces: Vec<TextPiece>,
+    /// (character, marker) of all pieces
+    pub markers: Vec<(usize, TextMarker)>,
+    /// The next marker to meet
+    pub marker: usize,
+    /// Seconds per character without `TextMarker::Speed`
+    pub speed: f32,
     pub timer: Timer,
     pub style: TextStyle,
+    pub fonts: FlowFonts,
     pub chars: usize,
+    /// Seconds left of `TextMarker::Wait`
+    pub wait: f32,
+    /// Stopped at `TextMarker::Pause` until click
+    pub is_paused: bool,
+    /// Met `TextMarker::NoWait`
+    pub auto_next: bool,
+    /// Seconds since start, drives effects
+    pub elapsed: f32,
+    /// Shown before typed text, passed texts of NVL page
+    pub page: Vec<TextSection>,
+}
+
+impl AnimateText {
+    pub fn new(text: &str, speed: f32, style: TextStyle, fonts: FlowFonts) -> Self {
+        let pieces = parse_markup(text);
+        let mut markers = vec![];
+        let mut chars = 0;
+        for piece in &pieces {
+            markers.extend(piece.markers.iter().map(|marker| { (chars, *marker) }));
+            chars += piece.text.chars().count();
+        }
+        AnimateText {
+            pieces,
+            markers,
+            marker: 0,
+            speed,
+            timer: Timer::from_seconds(speed, TimerMode::Repeating),
+            style,
+            fonts,
+            chars: 0,
+            wait: 0.0,
+            is_paused: false,
+            auto_next: false,
+            elapsed: 0.0,
+            page: vec![],
+        }
+    }
+
+    pub fn with_page(mut self, page: Vec<TextSection>) -> Self {
+        self.page = page;
+        self
+    }
+
+    /// Characters to show, annotations excluded
+    pub fn total(&self) -> usize {
+        self.pieces.iter().map(|piece| { piece.text.chars().count() }).sum()
+    }
+
+    /// Type characters for passed time, false when typewriter stands still.
+    /// `pass` shows everything up to the next `TextMarker::Pause`
+    pub fn advance(&mut self, delta: Duration, pass: bool) -> bool {
+        let total = self.total();
+        loop {
+            while let Some(&(position, marker)) = self.markers.get(self.marker) {
+                if position > self.chars { break; }
+                match marker {
+                    TextMarker::Pause => {
+                        self.is_paused = true;
+                        return false;
+                    }
+                    TextMarker::Wait(seconds) => {
+                        if !pass { self.wait = seconds; }
+                    }
+                    TextMarker::Speed(speed) => {
+                        self.timer.set_duration(Duration::from_secs_f32(self.speed / speed));
+                    }
+                    TextMarker::NoWait => { self.auto_next = true; }
+                }
+                self.marker += 1;
+            }
+            if self.chars >= total { return false; }
+            if !pass { break; }
+            self.chars = self.markers[self.marker..].iter()
+                .find(|(_, marker)| { *marker == TextMarker::Pause })
+                .map_or(total, |(position, _)| { *position });
+        }
+
+        if self.wait > 0.0 {
+            self.wait -= delta.as_secs_f32();
+            return true;
+        }
+        // stop at the next marker to meet it in time
+        let limit = self.markers.get(self.marker).map_or(total, |(position, _)| { *position });
+        let typed = self.timer.tick(delta).times_finished_this_tick() as usize;
+        self.chars = (self.chars + typed).min(limit);
+        true
+    }
+
+    /// Go on after `TextMarker::Pause`
+    pub fn resume(&mut self) {
+        if self.is_paused {
+            self.is_paused = false;
+            self.marker += 1;
+        }
+    }
+
+    pub fn has_effects(&self) -> bool {
+        self.pieces.iter().any(|piece| { piece.mark.effect.is_some() })
+    }
+}
+
+/// Alternatives of flow font for markup
+#[derive(Debug, Clone)]
+pub struct FlowFonts {
+    pub bold: Handle<Font>,
+    pub italic: Handle<Font>,
+    pub bold_italic: Handle<Font>,
 }
 // endregion
 
 // region phrase
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Component)]
 pub struct PhraseValue(pub String);
+
+/// Shrinking bar of timed `Steps::Phrase`
+#[derive(Debug, Clone, Component)]
+pub struct PhraseTimer {
+    pub timer: Timer,
+    /// Option selected when time runs out
+    pub default: String,
+    /// Bar width in percent of window when full
+    pub width: f32,
+}
+// endregion
+
+// region input
+/// Text typed into `Steps::Input`
+#[derive(Debug, Clone, Component)]
+pub struct InputField {
+    pub var: String,
+    pub value: String,
+    pub max_len: Option<usize>,
+}
+// endregion
+
+// region image select
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Component)]
+pub struct ImageSelectValue(pub String);
 // endregion
 
 // region narrator
@@ -35,6 +179,27 @@ pub struct NarratorPlaceholderMark;
 // region background
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Component)]
 pub struct BackgroundMark;
+
+/// Transition of background or of a copy of the previous one
+#[derive(Debug, Clone, Component)]
+pub struct AnimateBackground {
+    pub timer: Timer,
+    pub easing: Easing,
+    /// Part of the timer to animate in, from and to
+    pub span: (f32, f32),
+    pub alpha: (f32, f32),
+    /// Horizontal offset in window widths
+    pub offset: (f32, f32),
+    /// Copy of the previous background, gone after animation
+    pub despawn: bool,
+}
+
+#[derive(Debug, Clone, Component)]
+pub struct ShakeBackground {
+    pub timer: Timer,
+    /// Part of window width
+    pub strength: f32,
+}
 // endregion
 
 // region scene
@@ -42,10 +207,77 @@ pub struct BackgroundMark;
 pub struct SceneMark;
 // endregion
 
+// region particles
+/// Emitter of one kind of particles, they are its children
+#[derive(Debug, Clone, Component)]
+pub struct ParticleEmitter {
+    pub kind: ParticleKind,
+    pub intensity: f32,
+    pub wind: f32,
+    pub texture: Option<Handle<Image>>,
+    /// Particles owed for passed time
+    pub pending: f32,
+    /// Emits nothing, gone with the last particle
+    pub is_stopped: bool,
+    /// xorshift state, never zero
+    pub seed: u64,
+}
+
+impl ParticleEmitter {
+    /// Random value of `from..to`
+    pub fn random(&mut self, from: f32, to: f32) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        from + (to - from) * (self.seed >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
+
+#[derive(Debug, Clone, Component)]
+pub struct Particle {
+    pub kind: ParticleKind,
+    /// Pixels per second
+    pub velocity: Vec2,
+    /// Radians per second
+    pub spin: f32,
+    /// Side swing, (pixels per second, phase)
+    pub sway: (f32, f32),
+    pub alpha: f32,
+    pub age: f32,
+    /// Seconds, falls out of window first when infinite
+    pub life: f32,
+}
+// endregion
+
+// region screen
+/// Zoom and pan of camera
+#[derive(Debug, Clone, Component)]
+pub struct AnimateCamera {
+    pub timer: Timer,
+    pub easing: Easing,
+    /// (translation, projection scale) taken at first frame
+    pub start: Option<(Vec2, f32)>,
+    pub end: (Vec2, f32),
+}
+
+/// Color of fade, flash or vignette overlay
+#[derive(Debug, Clone, Component)]
+pub struct AnimateOverlay {
+    pub timer: Timer,
+    pub easing: Easing,
+    /// Taken at first frame
+    pub start: Option<Color>,
+    pub end: Color,
+}
+// endregion
+
 // region sprite
 #[derive(Debug, Clone, Component)]
 pub struct AnimateFadeSprite {
     pub timer: Timer,
+    pub easing: Easing,
+    /// Opacity of shown sprite, NaN to take current
+    pub alpha: f32,
     pub fade_in: bool,
     pub name: String,
 }
@@ -53,17 +285,46 @@ pub struct AnimateFadeSprite {
 #[derive(Debug, Clone, Component)]
 pub struct AnimateMoveSprite {
     pub timer: Timer,
+    pub easing: Easing,
     pub start_pos: f32,
     pub end_pos: f32,
     pub name: String,
     pub move_out: bool,
 }
+
+/// Sprite with layers of [`aerugo::Character`] as children
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Component)]
+pub struct CharacterMark;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Component)]
+pub enum CharacterLayer {
+    Outfit,
+    Expression,
+}
+
+#[derive(Debug, Clone, Component)]
+pub struct AnimateTransformSprite {
+    pub timer: Timer,
+    pub easing: Easing,
+    /// (y, scale, tint) taken at first frame
+    pub start: Option<(f32, f32, Color)>,
+    pub end: (f32, f32, Color),
+}
 // endregion
 
 
 #[derive(Debug, Clone, Component)]
 pub struct AnimateScene {
+    /// One pass over the frames
     pub timer: Timer,
+    pub easing: Easing,
     pub is_loop: bool,
     pub is_paused: bool,
+    /// First and last frame
+    pub frames: (usize, usize),
+    pub ping_pong: bool,
+    /// Goes back to the first frame of ping-pong
+    pub is_backward: bool,
+    /// Blocks text until finished
+    pub wait: bool,
 }
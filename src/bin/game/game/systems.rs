@@ -3,17 +3,20 @@ use std::io::Read;
 use bevy::{
     prelude::*,
 };
+use bevy::audio::AudioSink;
 use bevy::ecs::schedule::IntoRunCriteria;
+use bevy::input::mouse::MouseWheel;
 use bevy::log::Level;
 use bevy::utils::tracing::span;
 use substring::Substring;
+use uuid::Uuid;
 
 use aerugo::*;
 use aerugo::bevy_glue::GameMenuButtons;
 
 use crate::saves::AerugoLoaded;
 use crate::settings::Settings;
-use crate::ui::{GameUI, UiState};
+use crate::ui::{GameUI, ImageSelectUI, UiState};
 use crate::utils::*;
 
 use super::*;
@@ -40,9 +43,16 @@ pub fn setup_game(
     let window = windows.get_primary().unwrap();
 
     let aerugo_state = aerugo_loaded
-        .map(|loaded| { loaded.0.to_owned() })
+        .map(|loaded| { Ok(loaded.0.to_owned()) })
         .unwrap_or_else(|| { AerugoState::setup(aerugo.as_ref()) });
     commands.remove_resource::<AerugoLoaded>();
+    let aerugo_state = match aerugo_state {
+        Ok(aerugo_state) => { aerugo_state }
+        Err(error) => {
+            commands.insert_resource(AerugoFailure { error, fatal: false });
+            return;
+        }
+    };
 
     commands.insert_resource(aerugo_state);
     commands.insert_resource(JustInit);
@@ -64,6 +74,9 @@ pub fn setup_game(
     visibility_query.get_mut(game_ui.background).unwrap().is_visible = false;
     visibility_query.get_mut(game_ui.scene).unwrap().is_visible = false;
     game_ui.sprites = Default::default();
+    game_ui.particles.drain().for_each(|(_, e)| {
+        commands.entity(e).despawn_recursive();
+    });
     game_ui.text.clean_narrators(
         &mut style_query,
         &mut ui_image_query,
@@ -100,23 +113,39 @@ pub fn next_step_listener(
     aerugo: Res<Aerugo>,
     mut new_narrator_event: EventWriter<NewNarratorEvent>,
     mut new_sprite_event: EventWriter<NewSpriteEvent>,
+    mut new_character_event: EventWriter<NewCharacterEvent>,
     mut new_background_event: EventWriter<NewBackgroundEvent>,
     mut new_scene_event: EventWriter<NewSceneEvent>,
+    mut new_screen_event: EventWriter<NewScreenEvent>,
+    mut new_particles_event: EventWriter<NewParticlesEvent>,
+    mut new_music_event: EventWriter<NewMusicEvent>,
+    mut new_sound_event: EventWriter<NewSoundEvent>,
     just_init: Option<Res<JustInit>>,
 )
 {
     if events.iter().count() > 0 {
         match just_init {
             None => {
-                if aerugo_state.next(aerugo.as_ref()).is_none() {
-                    return;
+                match aerugo_state.next(aerugo.as_ref()) {
+                    Ok(_) => {}
+                    Err(AerugoError::EndOfScenario) => { return; }
+                    Err(error) => {
+                        commands.insert_resource(AerugoFailure { error, fatal: false });
+                        return;
+                    }
                 }
             }
             Some(_) => {
                 commands.remove_resource::<JustInit>();
             }
         }
-        let steps = aerugo_state.collect(aerugo.as_ref());
+        let steps = match aerugo_state.collect(aerugo.as_ref()) {
+            Ok(steps) => { steps }
+            Err(error) => {
+                commands.insert_resource(AerugoFailure { error, fatal: false });
+                return;
+            }
+        };
 
         // send events to update graphic part
         for step in steps {
@@ -127,18 +156,35 @@ pub fn next_step_listener(
                 Steps::Sprite(command) => {
                     new_sprite_event.send(NewSpriteEvent(command));
                 }
+                Steps::Character(command) => {
+                    new_character_event.send(NewCharacterEvent(command));
+                }
                 Steps::Background(command) => {
                     new_background_event.send(NewBackgroundEvent(command));
                 }
                 Steps::Scene(command) => {
                     new_scene_event.send(NewSceneEvent(command));
                 }
+                Steps::Screen(command) => {
+                    new_screen_event.send(NewScreenEvent(command));
+                }
+                Steps::Particles(command) => {
+                    new_particles_event.send(NewParticlesEvent(command));
+                }
+                Steps::Music(command) => {
+                    new_music_event.send(NewMusicEvent(command));
+                }
+                Steps::Sound { sound } => {
+                    new_sound_event.send(NewSoundEvent(sound));
+                }
                 _ => {}
             }
         }
 
-        let step = aerugo_state.step(aerugo.as_ref());
-        commands.insert_resource(step);
+        match aerugo_state.step(aerugo.as_ref()) {
+            Ok(step) => { commands.insert_resource(step); }
+            Err(error) => { commands.insert_resource(AerugoFailure { error, fatal: false }); }
+        }
     }
 }
 
@@ -189,24 +235,140 @@ pub fn new_narrator_listener(
 }
 
 pub fn new_background_listener(
+    mut commands: Commands,
     mut game_ui: ResMut<GameUI>,
     mut new_background_event: EventReader<NewBackgroundEvent>,
-    mut background_query: Query<(&mut Handle<Image>, &mut Visibility)>,
+    mut background_query: Query<(&mut Handle<Image>, &mut Visibility, &mut Sprite, &mut Transform)>,
+    animate_query: Query<(Entity, &AnimateBackground)>,
     mut asset_server: CachedAssetServer,
+    window: Res<Windows>,
 )
 {
+    const TRANSITION_DURATION: f32 = 1.0;
+    const SHAKE_STRENGTH: f32 = 0.02;
+    const SHAKE_DURATION: f32 = 0.5;
+
+    let window = window.get_primary().unwrap();
+    let w = window.width();
+    let h = window.height();
+
     for event in new_background_event.iter() {
-        let (mut background, mut visibility): (Mut<Handle<Image>>, Mut<Visibility>) =
-            background_query.get_mut(game_ui.background).unwrap();
         let cmd: &BackgroundCommand = &event.0;
+        if let BackgroundCommand::Change { .. } | BackgroundCommand::None = cmd {
+            // finish previous transition at once
+            for (entity, animate) in animate_query.iter() {
+                if animate.despawn {
+                    commands.entity(entity).despawn_recursive();
+                } else {
+                    commands.entity(entity).remove::<AnimateBackground>();
+                }
+            }
+        }
+        let (mut background, mut visibility, mut sprite, mut transform): (
+            Mut<Handle<Image>>,
+            Mut<Visibility>,
+            Mut<Sprite>,
+            Mut<Transform>,
+        ) = background_query.get_mut(game_ui.background).unwrap();
         match cmd {
-            BackgroundCommand::Change { new, .. } => {
+            BackgroundCommand::Change { new, animation, duration, easing } => {
+                let previous = if game_ui.background_visible {
+                    Some(background.clone())
+                } else {
+                    None
+                };
                 *background = asset_server.load(new);
                 visibility.is_visible = true;
                 game_ui.background_visible = true;
+                sprite.color.set_a(1.0);
+                transform.translation.x = 0.0;
+
+                let animation = animation.clone().unwrap_or_default();
+                let duration = duration.unwrap_or(TRANSITION_DURATION).max(0.0);
+                const WHOLE: (f32, f32) = (0.0, 1.0);
+                const OPAQUE: (f32, f32) = (1.0, 1.0);
+                const STILL: (f32, f32) = (0.0, 0.0);
+                // (span, alpha, offset) of new background and of previous one above or below it
+                let (new, old, old_above) = match animation {
+                    CommonAnimation::FadeIn(_) => {
+                        if previous.is_some() {
+                            (None, Some((WHOLE, (1.0, 0.0), STILL)), true)
+                        } else {
+                            (Some((WHOLE, (0.0, 1.0), STILL)), None, true)
+                        }
+                    }
+                    // through black
+                    CommonAnimation::FadeOut => {
+                        (
+                            Some(((0.5, 1.0), (0.0, 1.0), STILL)),
+                            Some(((0.0, 0.5), (1.0, 0.0), STILL)),
+                            true,
+                        )
+                    }
+                    CommonAnimation::LeftIn(_) => {
+                        (Some((WHOLE, OPAQUE, (-1.0, 0.0))), Some((WHOLE, OPAQUE, STILL)), false)
+                    }
+                    CommonAnimation::RightIn(_) => {
+                        (Some((WHOLE, OPAQUE, (1.0, 0.0))), Some((WHOLE, OPAQUE, STILL)), false)
+                    }
+                    CommonAnimation::LeftOut => {
+                        (None, Some((WHOLE, OPAQUE, (0.0, -1.0))), true)
+                    }
+                    CommonAnimation::RightOut => {
+                        (None, Some((WHOLE, OPAQUE, (0.0, 1.0))), true)
+                    }
+                    // push previous out to the left
+                    CommonAnimation::Move(_) => {
+                        (Some((WHOLE, OPAQUE, (1.0, 0.0))), Some((WHOLE, OPAQUE, (0.0, -1.0))), true)
+                    }
+                    CommonAnimation::Jump | CommonAnimation::None => { (None, None, true) }
+                };
+
+                if let Some((span, alpha, offset)) = new {
+                    sprite.color.set_a(alpha.0);
+                    transform.translation.x = offset.0 * w;
+                    commands.entity(game_ui.background).insert(AnimateBackground {
+                        timer: Timer::from_seconds(duration, TimerMode::Once),
+                        easing: *easing,
+                        span,
+                        alpha,
+                        offset,
+                        despawn: false,
+                    });
+                }
+                if let (Some(previous), Some((span, alpha, offset))) = (previous, old) {
+                    let z = if old_above { Z_BACKGROUND + 0.5 } else { Z_BACKGROUND - 0.5 };
+                    commands
+                        .spawn(SpriteBundle {
+                            sprite: Sprite {
+                                custom_size: Some(Vec2::new(w, h)),
+                                ..default()
+                            },
+                            texture: previous,
+                            transform: Transform::from_xyz(offset.0 * w, 0.0, z),
+                            ..default()
+                        })
+                        .insert(AnimateBackground {
+                            timer: Timer::from_seconds(duration, TimerMode::Once),
+                            easing: *easing,
+                            span,
+                            alpha,
+                            offset,
+                            despawn: true,
+                        });
+                }
             }
             BackgroundCommand::Shake => {
-                unimplemented!("Unimplemented 'Shake'")
+                commands.entity(game_ui.background).insert(ShakeBackground {
+                    timer: Timer::from_seconds(SHAKE_DURATION, TimerMode::Once),
+                    strength: SHAKE_STRENGTH,
+                });
+            }
+            BackgroundCommand::ShakeWith { strength, duration } => {
+                commands.entity(game_ui.background).insert(ShakeBackground {
+                    timer: Timer::from_seconds(duration.max(0.0), TimerMode::Once),
+                    strength: *strength,
+                });
             }
             BackgroundCommand::None => {
                 *background = Default::default();
@@ -228,6 +390,10 @@ pub fn new_scene_listener(
     window: Res<Windows>,
 )
 {
+    const FPS: f32 = 24.0;
+    // also catches zero, negative and NaN fps
+    const MIN_FPS: f32 = 0.1;
+
     let window = window.get_primary().unwrap();
     let w = window.width();
     let h = window.height();
@@ -251,7 +417,19 @@ pub fn new_scene_listener(
                 commands.entity(game_ui.scene).remove::<Handle<TextureAtlas>>();
                 commands.entity(game_ui.scene).remove::<AnimateScene>();
             }
-            SceneCommand::Play { name, is_loop, tile, columns, rows } => {
+            SceneCommand::Play {
+                name, is_loop, tile, columns, rows, fps, easing, frames, ping_pong, wait, from,
+            } => {
+                let last = (columns * rows).max(1) - 1;
+                let frames = frames
+                    .map(|(first, end)| (first.min(last), end.clamp(first.min(last), last)))
+                    .unwrap_or((0, last));
+                let index = from.unwrap_or(frames.0).clamp(frames.0, frames.1);
+                let count = (frames.1 - frames.0 + 1) as f32;
+                let fps = fps.unwrap_or(FPS).max(MIN_FPS);
+                let mut timer = Timer::from_seconds(count / fps, TimerMode::Once);
+                // resume loaded game at its frame
+                timer.set_elapsed(timer.duration().mul_f32((index - frames.0) as f32 / count));
                 let texture_handle = asset_server.load(name);
                 let new_texture_atlas = TextureAtlas::from_grid(
                     texture_handle,
@@ -264,12 +442,18 @@ pub fn new_scene_listener(
                 commands
                     .entity(game_ui.scene)
                     .insert(AnimateScene {
-                        timer: Timer::from_seconds(0.042, TimerMode::Repeating),
+                        timer,
+                        easing: *easing,
                         is_loop: *is_loop,
                         is_paused: false,
+                        frames,
+                        ping_pong: *ping_pong,
+                        is_backward: false,
+                        wait: *wait,
                     })
                     .insert(texture_atlases.add(new_texture_atlas))
                     .insert(TextureAtlasSprite {
+                        index,
                         custom_size: Some(Vec2::new(w, h)),
                         ..default()
                     });
@@ -294,11 +478,152 @@ pub fn new_scene_listener(
     }
 }
 
+pub fn new_screen_listener(
+    mut commands: Commands,
+    game_ui: Res<GameUI>,
+    mut new_screen_event: EventReader<NewScreenEvent>,
+    camera_query: Query<(Entity, &OrthographicProjection, Option<&AnimateCamera>), With<Camera2d>>,
+    overlay_query: Query<(&BackgroundColor, Option<&AnimateOverlay>)>,
+    window: Res<Windows>,
+)
+{
+    let window = window.get_primary().unwrap();
+    let size = Vec2::new(window.width(), window.height());
+    let timer = |duration: f32| { Timer::from_seconds(duration.max(0.0), TimerMode::Once) };
+
+    for event in new_screen_event.iter() {
+        let cmd: &ScreenCommand = &event.0;
+        let (camera, projection, animate) = match camera_query.get_single() {
+            Ok(camera) => { camera }
+            Err(e) => {
+                warn!("{e:?}");
+                continue;
+            }
+        };
+        let mut move_camera = |x: f32, y: f32, scale: f32, duration: &Option<f32>, easing| {
+            commands.entity(camera).insert(AnimateCamera {
+                timer: timer(duration.unwrap_or(0.0)),
+                easing,
+                start: None,
+                end: (Vec2::new(x, y) * size, 1.0 / scale.max(0.01)),
+            });
+        };
+        match cmd {
+            ScreenCommand::Zoom { x, y, scale, duration, easing } => {
+                move_camera(*x, *y, *scale, duration, *easing);
+            }
+            ScreenCommand::Pan { x, y, duration, easing } => {
+                let scale = animate.map_or(projection.scale, |animate| { animate.end.1 });
+                move_camera(*x, *y, 1.0 / scale, duration, *easing);
+            }
+            ScreenCommand::Reset { duration, easing } => {
+                move_camera(0.0, 0.0, 1.0, duration, *easing);
+            }
+            ScreenCommand::Flash { color, duration } => {
+                let [r, g, b, a] = *color;
+                commands.entity(game_ui.screen.flash).insert(AnimateOverlay {
+                    timer: timer(*duration),
+                    easing: Easing::Linear,
+                    start: Some(Color::rgba(r, g, b, a)),
+                    end: Color::rgba(r, g, b, 0.0),
+                });
+            }
+            ScreenCommand::FadeTo { color, duration, easing } => {
+                let [r, g, b, a] = *color;
+                commands.entity(game_ui.screen.fade).insert(AnimateOverlay {
+                    timer: timer(duration.unwrap_or(0.0)),
+                    easing: *easing,
+                    start: None,
+                    end: Color::rgba(r, g, b, a),
+                });
+            }
+            ScreenCommand::FadeFrom { duration, easing } => {
+                // keeps color of fade, only alpha goes away
+                let mut end = overlay_query.get(game_ui.screen.fade)
+                    .map(|(color, animate)| { animate.map_or(color.0, |animate| { animate.end }) })
+                    .unwrap_or(TRANSPARENT);
+                end.set_a(0.0);
+                commands.entity(game_ui.screen.fade).insert(AnimateOverlay {
+                    timer: timer(duration.unwrap_or(0.0)),
+                    easing: *easing,
+                    start: None,
+                    end,
+                });
+            }
+            ScreenCommand::Vignette { strength, duration, easing } => {
+                commands.entity(game_ui.screen.vignette).insert(AnimateOverlay {
+                    timer: timer(duration.unwrap_or(0.0)),
+                    easing: *easing,
+                    start: None,
+                    end: Color::rgba(1.0, 1.0, 1.0, strength.clamp(0.0, 1.0)),
+                });
+            }
+            ScreenCommand::None => {}
+        }
+    }
+}
+
+pub fn new_particles_listener(
+    mut commands: Commands,
+    mut game_ui: ResMut<GameUI>,
+    mut new_particles_event: EventReader<NewParticlesEvent>,
+    mut asset_server: CachedAssetServer,
+    mut emitter_query: Query<&mut ParticleEmitter>,
+)
+{
+    for event in new_particles_event.iter() {
+        let cmd: &ParticlesCommand = &event.0;
+        match cmd {
+            ParticlesCommand::Start { kind, intensity, wind, sprite } => {
+                let emitter = ParticleEmitter {
+                    kind: *kind,
+                    intensity: *intensity,
+                    wind: *wind,
+                    texture: sprite.as_ref().map(|sprite| { asset_server.load(sprite) }),
+                    pending: 0.0,
+                    is_stopped: false,
+                    seed: Uuid::new_v4().as_u64_pair().0 | 1,
+                };
+                // flying particles stay with replaced emitter
+                match game_ui.particles.get(kind) {
+                    Some(&entity) => {
+                        match emitter_query.get_mut(entity) {
+                            Ok(mut current) => { *current = emitter; }
+                            Err(_) => { commands.entity(entity).insert(emitter); }
+                        }
+                    }
+                    None => {
+                        let entity = commands
+                            .spawn(SpatialBundle {
+                                transform: Transform::from_xyz(0.0, 0.0, Z_PARTICLES),
+                                ..default()
+                            })
+                            .insert(emitter)
+                            .id();
+                        game_ui.particles.insert(*kind, entity);
+                    }
+                }
+            }
+            ParticlesCommand::Stop { kind } => {
+                for (_, entity) in game_ui.particles.iter()
+                    .filter(|(k, _)| { kind.map_or(true, |kind| { kind.eq(k) }) })
+                {
+                    if let Ok(mut emitter) = emitter_query.get_mut(*entity) {
+                        emitter.is_stopped = true;
+                    }
+                }
+            }
+            ParticlesCommand::None => {}
+        }
+    }
+}
+
 pub fn new_sprite_listener(
     mut commands: Commands,
     mut game_ui: ResMut<GameUI>,
     mut new_sprite_event: EventReader<NewSpriteEvent>,
     mut asset_server: CachedAssetServer,
+    mut sprite_query: Query<(&mut Transform, &mut Sprite)>,
     window: Res<Windows>,
 )
 {
@@ -309,35 +634,45 @@ pub fn new_sprite_listener(
     const RIGHT_IN_DURATION: f32 = 1.0;
     const RIGHT_OUT_DURATION: f32 = 1.0;
     const MOVE_DURATION: f32 = 1.0;
+    const TRANSFORM_DURATION: f32 = 0.5;
 
     let window = window.get_primary().unwrap();
     let w = window.width();
     let h = window.height();
     let w_half = w / 2.0;
 
-    let sprite_def = Sprite {
-        custom_size: Some(Vec2::new(w, h)),
-        ..default()
+    let timer = |duration: &Option<f32>, default: f32| {
+        Timer::from_seconds(duration.unwrap_or(default).max(0.0), TimerMode::Once)
     };
 
+    let size = Vec2::new(w, h);
+    let place = |x: f32, transform: &SpriteTransform| { sprite_place(x, transform, size) };
+
     for event in new_sprite_event.iter() {
         let cmd: &SpriteCommand = &event.0;
+        // flat sprite replaces layers of character
+        if let SpriteCommand::Set { name, .. }
+        | SpriteCommand::FadeIn { name, .. }
+        | SpriteCommand::LeftIn { name, .. }
+        | SpriteCommand::RightIn { name, .. } = cmd {
+            if let Some(entity) = game_ui.sprites.get(name) {
+                commands.entity(*entity).remove::<CharacterMark>().despawn_descendants();
+            }
+        }
         match cmd {
-            SpriteCommand::Set { sprite, name, position } => {
+            SpriteCommand::Set { sprite, name, position, transform } => {
                 let sprite: Handle<Image> = asset_server.load(sprite);
                 let mut entity_cmd = match game_ui.sprites.get_mut(name) {
                     None => {
-                        commands.spawn(SpriteBundle {
-                            sprite: sprite_def.clone(),
-                            ..default()
-                        })
+                        commands.spawn(SpriteBundle::default())
                     }
                     Some(entity) => {
                         commands.entity(*entity)
                     }
                 };
                 entity_cmd.insert(sprite);
-                entity_cmd.insert(Transform::from_xyz(w_half * position, Y_SPRITE, Z_SPRITE));
+                entity_cmd.insert(sprite_look(transform));
+                entity_cmd.insert(place(w_half * position, transform));
                 game_ui.sprites.insert(name.clone(), entity_cmd.id());
             }
             SpriteCommand::Remove { name } => {
@@ -352,57 +687,58 @@ pub fn new_sprite_listener(
                         Option::<Entity>::None
                     });
             }
-            SpriteCommand::FadeIn { sprite, name, position } => {
+            SpriteCommand::FadeIn { sprite, name, position, transform, duration, easing } => {
                 let sprite: Handle<Image> = asset_server.load(sprite);
                 let mut entity_cmd = match game_ui.sprites.get_mut(name) {
                     None => {
-                        commands.spawn(SpriteBundle {
-                            sprite: sprite_def.clone(),
-                            ..default()
-                        })
+                        commands.spawn(SpriteBundle::default())
                     }
                     Some(entity) => { commands.entity(*entity) }
                 };
                 let entity = entity_cmd
                     .insert(sprite)
-                    .insert(Transform::from_xyz(w_half * position, Y_SPRITE, Z_SPRITE))
+                    .insert(sprite_look(transform))
+                    .insert(place(w_half * position, transform))
                     .insert(AnimateFadeSprite {
-                        timer: Timer::from_seconds(FADE_IN_DURATION, TimerMode::Once),
+                        timer: timer(duration, FADE_IN_DURATION),
+                        easing: *easing,
+                        alpha: transform.tint[3],
                         fade_in: true,
                         name: name.clone(),
                     })
                     .id();
                 game_ui.sprites.insert(name.clone(), entity);
             }
-            SpriteCommand::FadeOut { name } => {
+            SpriteCommand::FadeOut { name, duration, easing } => {
                 game_ui.sprites.get(name)
                     .and_then(|e| {
                         commands
                             .entity(e.clone())
                             .insert(AnimateFadeSprite {
-                                timer: Timer::from_seconds(FADE_OUT_DURATION, TimerMode::Once),
+                                timer: timer(duration, FADE_OUT_DURATION),
+                                easing: *easing,
+                                alpha: f32::NAN,
                                 fade_in: false,
                                 name: name.clone(),
                             });
                         Some(e)
                     });
             }
-            SpriteCommand::LeftIn { sprite, name, position } => {
+            SpriteCommand::LeftIn { sprite, name, position, transform, duration, easing } => {
                 let sprite: Handle<Image> = asset_server.load(sprite);
                 let mut entity_cmd = match game_ui.sprites.get_mut(name) {
                     None => {
-                        commands.spawn(SpriteBundle {
-                            sprite: sprite_def.clone(),
-                            ..default()
-                        })
+                        commands.spawn(SpriteBundle::default())
                     }
                     Some(entity) => { commands.entity(*entity) }
                 };
                 let entity = entity_cmd
                     .insert(sprite)
-                    .insert(Transform::from_xyz(w_half * -2.0, Y_SPRITE, Z_SPRITE))
+                    .insert(sprite_look(transform))
+                    .insert(place(w_half * -2.0, transform))
                     .insert(AnimateMoveSprite {
-                        timer: Timer::from_seconds(LEFT_IN_DURATION, TimerMode::Once),
+                        timer: timer(duration, LEFT_IN_DURATION),
+                        easing: *easing,
                         start_pos: f32::NEG_INFINITY,
                         end_pos: w_half * position,
                         name: name.clone(),
@@ -411,13 +747,14 @@ pub fn new_sprite_listener(
                     .id();
                 game_ui.sprites.insert(name.clone(), entity);
             }
-            SpriteCommand::LeftOut { name } => {
+            SpriteCommand::LeftOut { name, duration, easing } => {
                 game_ui.sprites.get(name)
                     .and_then(|e| {
                         commands
                             .entity(e.clone())
                             .insert(AnimateMoveSprite {
-                                timer: Timer::from_seconds(LEFT_OUT_DURATION, TimerMode::Once),
+                                timer: timer(duration, LEFT_OUT_DURATION),
+                                easing: *easing,
                                 start_pos: f32::NAN,
                                 end_pos: f32::NEG_INFINITY,
                                 name: name.clone(),
@@ -426,22 +763,21 @@ pub fn new_sprite_listener(
                         Some(e)
                     });
             }
-            SpriteCommand::RightIn { sprite, name, position } => {
+            SpriteCommand::RightIn { sprite, name, position, transform, duration, easing } => {
                 let sprite: Handle<Image> = asset_server.load(sprite);
                 let mut entity_cmd = match game_ui.sprites.get_mut(name) {
                     None => {
-                        commands.spawn(SpriteBundle {
-                            sprite: sprite_def.clone(),
-                            ..default()
-                        })
+                        commands.spawn(SpriteBundle::default())
                     }
                     Some(entity) => { commands.entity(*entity) }
                 };
                 let entity = entity_cmd
                     .insert(sprite)
-                    .insert(Transform::from_xyz(w_half * 2.0, Y_SPRITE, Z_SPRITE))
+                    .insert(sprite_look(transform))
+                    .insert(place(w_half * 2.0, transform))
                     .insert(AnimateMoveSprite {
-                        timer: Timer::from_seconds(RIGHT_IN_DURATION, TimerMode::Once),
+                        timer: timer(duration, RIGHT_IN_DURATION),
+                        easing: *easing,
                         start_pos: f32::INFINITY,
                         end_pos: w_half * position,
                         name: name.clone(),
@@ -450,13 +786,14 @@ pub fn new_sprite_listener(
                     .id();
                 game_ui.sprites.insert(name.clone(), entity);
             }
-            SpriteCommand::RightOut { name } => {
+            SpriteCommand::RightOut { name, duration, easing } => {
                 game_ui.sprites.get(name)
                     .and_then(|e| {
                         commands
                             .entity(e.clone())
                             .insert(AnimateMoveSprite {
-                                timer: Timer::from_seconds(RIGHT_OUT_DURATION, TimerMode::Once),
+                                timer: timer(duration, RIGHT_OUT_DURATION),
+                                easing: *easing,
                                 start_pos: f32::NAN,
                                 end_pos: f32::INFINITY,
                                 name: name.clone(),
@@ -465,13 +802,14 @@ pub fn new_sprite_listener(
                         Some(e)
                     });
             }
-            SpriteCommand::Move { name, position } => {
+            SpriteCommand::Move { name, position, duration, easing } => {
                 game_ui.sprites.get(name)
                     .and_then(|e| {
                         commands
                             .entity(e.clone())
                             .insert(AnimateMoveSprite {
-                                timer: Timer::from_seconds(MOVE_DURATION, TimerMode::Once),
+                                timer: timer(duration, MOVE_DURATION),
+                                easing: *easing,
                                 start_pos: f32::NAN,
                                 end_pos: w_half * position,
                                 name: name.clone(),
@@ -480,11 +818,238 @@ pub fn new_sprite_listener(
                         Some(e)
                     });
             }
+            SpriteCommand::Transform { name, transform, duration, easing } => {
+                let entity = match game_ui.sprites.get(name) {
+                    None => {
+                        warn!("Invalid sprite name: {}", name);
+                        continue;
+                    }
+                    Some(entity) => { *entity }
+                };
+                let target = place(0.0, transform);
+                if let Ok((mut current, mut sprite)) = sprite_query.get_mut(entity) {
+                    current.translation.z = target.translation.z;
+                    sprite.flip_x = transform.flip;
+                }
+                commands.entity(entity).insert(AnimateTransformSprite {
+                    timer: timer(duration, TRANSFORM_DURATION),
+                    easing: *easing,
+                    start: None,
+                    end: (target.translation.y, transform.scale, sprite_tint(transform)),
+                });
+            }
             _ => {}
         }
     }
 }
 
+pub fn new_character_listener(
+    mut commands: Commands,
+    mut game_ui: ResMut<GameUI>,
+    aerugo: Res<Aerugo>,
+    mut new_character_event: EventReader<NewCharacterEvent>,
+    mut asset_server: CachedAssetServer,
+    children_query: Query<&Children>,
+    mut layer_query: Query<(&CharacterLayer, &mut Handle<Image>, &mut Visibility)>,
+    window: Res<Windows>,
+)
+{
+    let span = span!(Level::WARN, "new_character_listener");
+    let _enter = span.enter();
+
+    // layers over the base, under the next sprite layer
+    const LAYER_Z: f32 = Z_SPRITE_LAYER / 4.0;
+
+    let window = window.get_primary().unwrap();
+    let size = Vec2::new(window.width(), window.height());
+
+    for event in new_character_event.iter() {
+        let cmd: &CharacterCommand = &event.0;
+        let name = match cmd {
+            CharacterCommand::Show { name, .. }
+            | CharacterCommand::Outfit { name, .. }
+            | CharacterCommand::Expression { name, .. } => { name }
+            CharacterCommand::None => { continue; }
+        };
+        let character = match aerugo.characters.get(name) {
+            None => {
+                warn!("Unknown character: {}", name);
+                continue;
+            }
+            Some(character) => { character }
+        };
+        match cmd {
+            CharacterCommand::Show { outfit, expression, position, transform, duration, .. } => {
+                let x = size.x / 2.0 * position.unwrap_or(character.position);
+                let mut entity_cmd = match game_ui.sprites.get(name) {
+                    None => { commands.spawn(SpriteBundle::default()) }
+                    Some(entity) => { commands.entity(*entity) }
+                };
+                entity_cmd.despawn_descendants();
+                entity_cmd
+                    .insert(asset_server.load::<Image>(&character.base))
+                    .insert(sprite_look(transform))
+                    .insert(sprite_place(x, transform, size))
+                    .insert(CharacterMark);
+                if let Some(duration) = duration {
+                    entity_cmd.insert(AnimateFadeSprite {
+                        timer: Timer::from_seconds(duration.max(0.0), TimerMode::Once),
+                        easing: Easing::Linear,
+                        alpha: transform.tint[3],
+                        fade_in: true,
+                        name: name.clone(),
+                    });
+                }
+                let entity = entity_cmd.id();
+                let layers = [
+                    (CharacterLayer::Outfit, character.outfit(outfit)),
+                    (CharacterLayer::Expression, character.expression(expression)),
+                ];
+                for (z, (layer, file)) in layers.into_iter().enumerate() {
+                    let child = commands
+                        .spawn(SpriteBundle {
+                            sprite: sprite_look(transform),
+                            texture: file
+                                .map(|file| { asset_server.load(file) })
+                                .unwrap_or_default(),
+                            transform: Transform::from_xyz(0.0, 0.0, LAYER_Z * (z + 1) as f32),
+                            visibility: Visibility { is_visible: file.is_some() },
+                            ..default()
+                        })
+                        .insert(layer)
+                        .id();
+                    commands.entity(entity).add_child(child);
+                }
+                game_ui.sprites.insert(name.clone(), entity);
+            }
+            CharacterCommand::Outfit { outfit, .. } => {
+                swap_layer(
+                    name,
+                    (CharacterLayer::Outfit, character.outfit(outfit)),
+                    &game_ui,
+                    &children_query,
+                    &mut layer_query,
+                    &mut asset_server,
+                );
+            }
+            CharacterCommand::Expression { expression, .. } => {
+                swap_layer(
+                    name,
+                    (CharacterLayer::Expression, character.expression(expression)),
+                    &game_ui,
+                    &children_query,
+                    &mut layer_query,
+                    &mut asset_server,
+                );
+            }
+            CharacterCommand::None => {}
+        }
+    }
+}
+
+fn swap_layer(
+    name: &String,
+    (kind, file): (CharacterLayer, Option<&String>),
+    game_ui: &GameUI,
+    children_query: &Query<&Children>,
+    layer_query: &mut Query<(&CharacterLayer, &mut Handle<Image>, &mut Visibility)>,
+    asset_server: &mut CachedAssetServer,
+)
+{
+    let children = game_ui.sprites.get(name)
+        .and_then(|entity| { children_query.get(*entity).ok() });
+    let children = match children {
+        None => {
+            warn!("Character is not shown: {}", name);
+            return;
+        }
+        Some(children) => { children }
+    };
+    for child in children.iter() {
+        if let Ok((layer, mut texture, mut visibility)) = layer_query.get_mut(*child) {
+            if *layer == kind {
+                *texture = file.map(|file| { asset_server.load(file) }).unwrap_or_default();
+                visibility.is_visible = file.is_some();
+            }
+        }
+    }
+}
+
+/// Layers follow color, flip and size of character sprite
+pub fn sync_character_layers(
+    character_query: Query<(&Children, &Sprite), (With<CharacterMark>, Changed<Sprite>)>,
+    mut layer_query: Query<&mut Sprite, (With<CharacterLayer>, Without<CharacterMark>)>,
+)
+{
+    for (children, sprite) in character_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut layer) = layer_query.get_mut(*child) {
+                layer.color = sprite.color;
+                layer.flip_x = sprite.flip_x;
+                layer.custom_size = sprite.custom_size;
+            }
+        }
+    }
+}
+
+/// Sprites fill window height and keep aspect ratio of their image
+pub fn fit_sprites(
+    game_ui: Res<GameUI>,
+    images: Res<Assets<Image>>,
+    mut sprite_query: Query<(&Handle<Image>, &mut Sprite)>,
+    window: Res<Windows>,
+)
+{
+    let window = window.get_primary().unwrap();
+    let h = window.height();
+    for entity in game_ui.sprites.values() {
+        if let Ok((texture, mut sprite)) = sprite_query.get_mut(*entity) {
+            let image = match images.get(texture) {
+                None => { continue; }
+                Some(image) => { image.size() }
+            };
+            if image.y <= 0.0 {
+                continue;
+            }
+            let size = Some(Vec2::new(h * image.x / image.y, h));
+            if sprite.custom_size != size {
+                sprite.custom_size = size;
+            }
+        }
+    }
+}
+
+fn lerp_color(from: Color, to: Color, percent: f32) -> Color {
+    let lerp = |from: f32, to: f32| { from + (to - from) * percent };
+    Color::rgba(
+        lerp(from.r(), to.r()),
+        lerp(from.g(), to.g()),
+        lerp(from.b(), to.b()),
+        lerp(from.a(), to.a()),
+    )
+}
+
+fn sprite_tint(transform: &SpriteTransform) -> Color {
+    let [r, g, b, a] = transform.tint;
+    Color::rgba(r, g, b, a)
+}
+
+/// Size is set by [`fit_sprites`] once the image is loaded
+fn sprite_look(transform: &SpriteTransform) -> Sprite {
+    Sprite {
+        color: sprite_tint(transform),
+        flip_x: transform.flip,
+        ..default()
+    }
+}
+
+fn sprite_place(x: f32, transform: &SpriteTransform, size: Vec2) -> Transform {
+    let y = Y_SPRITE + size.y / 2.0 * transform.y;
+    let z = Z_SPRITE + Z_SPRITE_LAYER * transform.layer as f32;
+    Transform::from_xyz(x, y, z)
+        .with_scale(Vec3::new(transform.scale, transform.scale, 1.0))
+}
+
 pub fn step_init(
     mut commands: Commands,
     mut asset_server: CachedAssetServer,
@@ -493,6 +1058,10 @@ pub fn step_init(
     mut style_query: Query<&mut Style>,
     mut game_ui: ResMut<GameUI>,
     settings: Res<Settings>,
+    audio: Res<Audio>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    mut audio_channels: ResMut<AudioChannels>,
+    aerugo_state: Res<AerugoState>,
 )
 {
     let span = span!(Level::WARN, "step_init");
@@ -501,11 +1070,67 @@ pub fn step_init(
     if let Some(step) = step {
         // game_ui.text.force_hide(&mut style_query);
         game_ui.phrase.force_hide(&mut style_query);
+        game_ui.image_select.force_hide(&mut style_query);
+        game_ui.input.force_hide(&mut style_query);
+        audio_channels.stop_voice(&audio_sinks);
+
+        // text box or NVL page stays under other await steps
+        if aerugo_state.inspector.nvl {
+            game_ui.text.force_hide(&mut style_query);
+            game_ui.nvl.force_show(&mut style_query);
+        } else {
+            game_ui.nvl.force_hide(&mut style_query);
+        }
 
         match &step.inner {
-            Steps::Text { author, texts } => {
+            Steps::Text { author, texts, voice } if aerugo_state.inspector.nvl => {
+                let author = aerugo_state.interpolate(author);
+                let texts = aerugo_state.interpolate_markup(texts);
+                if let Some(voice) = voice {
+                    let sink = audio.play(asset_server.load(voice));
+                    audio_channels.voice = Some(audio_sinks.get_handle(sink));
+                }
+
+                let style = TextStyle {
+                    font: asset_server.load(FONT_FLOW),
+                    font_size: settings.flow_size,
+                    color: Color::WHITE,
+                };
+                let fonts = FlowFonts {
+                    bold: asset_server.load(FONT_FLOW_BOLD),
+                    italic: asset_server.load(FONT_FLOW_ITALIC),
+                    bold_italic: asset_server.load(FONT_FLOW_BOLD_ITALIC),
+                };
+                let mut page = vec![];
+                for (author, texts) in &aerugo_state.inspector.page {
+                    page.extend(author_section(author, &style, &fonts));
+                    let mut animate = AnimateText::new(texts, 0.0, style.clone(), fonts.clone());
+                    // effects of passed texts stand still
+                    animate.pieces.iter_mut().for_each(|piece| { piece.mark.effect = None; });
+                    animate.chars = animate.total();
+                    page.extend(text_sections(&animate));
+                    page.push(TextSection { value: "\n\n".to_string(), style: style.clone() });
+                }
+                page.extend(author_section(&author, &style, &fonts));
+
+                commands.entity(game_ui.text.text).remove::<AnimateText>();
+                commands
+                    .entity(game_ui.nvl.text)
+                    .insert(AnimateText::new(&texts, settings.flow_speed, style, fonts)
+                        .with_page(page));
+                game_control_state.overwrite_set(GameControlState::TextPass)
+                    .unwrap_or_else(|e| warn!("{e:?}"));
+            }
+            Steps::Text { author, texts, voice } => {
+                let author = aerugo_state.interpolate(author);
+                let texts = aerugo_state.interpolate_markup(texts);
                 game_ui.text.force_show(&mut style_query);
 
+                if let Some(voice) = voice {
+                    let sink = audio.play(asset_server.load(voice));
+                    audio_channels.voice = Some(audio_sinks.get_handle(sink));
+                }
+
                 if author.is_empty() {
                     style_query.get_mut(game_ui.text.narrator_base).unwrap()
                         .display = Display::None;
@@ -523,27 +1148,53 @@ pub fn step_init(
                             },
                         ));
                 }
+                commands.entity(game_ui.nvl.text).remove::<AnimateText>();
                 commands
                     .entity(game_ui.text.text)
-                    .insert(AnimateText {
-                        text: texts.clone(),
-                        timer: Timer::from_seconds(settings.flow_speed, TimerMode::Repeating),
-                        style: TextStyle {
+                    .insert(AnimateText::new(
+                        &texts,
+                        settings.flow_speed,
+                        TextStyle {
                             font: asset_server.load(FONT_FLOW),
                             font_size: settings.flow_size,
                             color: Color::BLACK,
                         },
-                        chars: 0,
-                    });
+                        FlowFonts {
+                            bold: asset_server.load(FONT_FLOW_BOLD),
+                            italic: asset_server.load(FONT_FLOW_ITALIC),
+                            bold_italic: asset_server.load(FONT_FLOW_BOLD_ITALIC),
+                        },
+                    ));
                 game_control_state.overwrite_set(GameControlState::TextPass)
                     .unwrap_or_else(|e| warn!("{e:?}"));
             }
-            Steps::Phrase { phrases } => {
+            Steps::Phrase { phrases, timeout } => {
                 game_ui.phrase.force_show(&mut style_query);
 
-                let phrase_options: Vec<Entity> = phrases.iter()
-                    .map(|o| {
-                        let (key, verbose) = o;
+                let mut phrase_options: Vec<Entity> = phrases.iter()
+                    .filter(|option| { option.is_visible(&aerugo_state) })
+                    .map(|option| {
+                        let is_available = option.is_available(&aerugo_state);
+                        let is_chosen = aerugo_state.was_chosen(&step.id, &option.key);
+                        let style = TextStyle {
+                            font: asset_server.load(FONT_DIALOG),
+                            font_size: 30.0,
+                            color: match (is_available, is_chosen) {
+                                (false, _) => { Color::GRAY }
+                                (true, true) => { Color::DARK_GRAY }
+                                (true, false) => { Color::BLACK }
+                            },
+                        };
+                        let mut text = Text::from_section(
+                            aerugo_state.interpolate(&option.verbose),
+                            style.clone(),
+                        );
+                        if let (false, Some(reason)) = (is_available, &option.disabled) {
+                            text.sections.push(TextSection {
+                                value: format!("\n{}", aerugo_state.interpolate(reason)),
+                                style: TextStyle { font_size: 20.0, ..style },
+                            });
+                        }
                         commands
                             .spawn(ImageBundle {
                                 style: Style {
@@ -555,11 +1206,14 @@ pub fn step_init(
                                     ..default()
                                 },
                                 image: asset_server.load("hud/game_option.png").into(),
+                                background_color: match is_available {
+                                    true => { Color::WHITE.into() }
+                                    false => { Color::rgba(1.0, 1.0, 1.0, 0.5).into() }
+                                },
                                 ..default()
                             })
-                            // .insert(PhraseValue(key.clone()))
                             .with_children(|parent| {
-                                parent
+                                let mut button = parent
                                     .spawn(ButtonBundle {
                                         style: Style {
                                             size: SIZE_ALL,
@@ -569,36 +1223,121 @@ pub fn step_init(
                                             ..default()
                                         },
                                         ..default()
-                                    })
-                                    .insert(PhraseValue(key.clone()))
-                                    .with_children(|parent| {
-                                        parent.spawn(TextBundle {
-                                            text: Text::from_section(
-                                                verbose.as_str(),
-                                                TextStyle {
-                                                    font: asset_server.load(FONT_DIALOG),
-                                                    font_size: 30.0,
-                                                    color: Color::BLACK,
-                                                },
-                                            ).with_alignment(TextAlignment {
-                                                vertical: VerticalAlign::Center,
-                                                horizontal: HorizontalAlign::Center,
-                                            }),
-                                            ..Default::default()
-                                        });
                                     });
+                                // greyed option takes no clicks
+                                match is_available {
+                                    true => { button.insert(PhraseValue(option.key.clone())); }
+                                    false => { button.insert(BackgroundColor(TRANSPARENT)); }
+                                }
+                                button.with_children(|parent| {
+                                    parent.spawn(TextBundle {
+                                        text: text.with_alignment(TextAlignment {
+                                            vertical: VerticalAlign::Center,
+                                            horizontal: HorizontalAlign::Center,
+                                        }),
+                                        ..Default::default()
+                                    });
+                                });
                             })
                             .id()
                     })
                     .collect();
+                // no countdown to an option the player could not select
+                let timeout = timeout.as_ref().filter(|timeout| {
+                    settings.choice_timers && phrases.iter().any(|option| {
+                        option.key == timeout.default && option.is_available(&aerugo_state)
+                    })
+                });
+                if let Some(timeout) = timeout {
+                    const WIDTH: f32 = 40.0;
+                    let bar = commands
+                        .spawn(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(WIDTH), Val::Px(6.0)),
+                                margin: UiRect::all(Val::Percent(1.0)),
+                                ..default()
+                            },
+                            background_color: Color::rgb(0.8, 0.1, 0.1).into(),
+                            ..default()
+                        })
+                        .insert(PhraseTimer {
+                            timer: Timer::from_seconds(timeout.seconds.max(0.0), TimerMode::Once),
+                            default: timeout.default.clone(),
+                            width: WIDTH,
+                        })
+                        .id();
+                    phrase_options.insert(0, bar);
+                }
                 commands.entity(game_ui.phrase.root).despawn_descendants();
                 commands.entity(game_ui.phrase.root).push_children(phrase_options.as_slice());
 
                 game_control_state.overwrite_set(GameControlState::Phrase)
                     .unwrap_or_else(|e| warn!("{e:?}"));
             }
-            Steps::ImageSelect { .. } => {
-                todo!("ImageSelect")
+            Steps::ImageSelect { background, options } => {
+                game_ui.text.force_hide(&mut style_query);
+                game_ui.image_select.force_show(&mut style_query);
+                commands
+                    .entity(game_ui.image_select.root)
+                    .insert(UiImage(asset_server.load(background)));
+
+                let mut keys: Vec<&String> = options.keys().collect();
+                keys.sort();
+                let select_options: Vec<Entity> = keys.into_iter()
+                    .map(|key| {
+                        let (sprite, (x, y)) = &options[key];
+                        commands
+                            .spawn(ImageBundle {
+                                style: Style {
+                                    position_type: PositionType::Absolute,
+                                    position: UiRect {
+                                        left: Val::Percent(*x as f32 / ImageSelectUI::REFERENCE.x * 100.0),
+                                        top: Val::Percent(*y as f32 / ImageSelectUI::REFERENCE.y * 100.0),
+                                        ..default()
+                                    },
+                                    ..default()
+                                },
+                                image: asset_server.load(sprite).into(),
+                                background_color: ImageSelectUI::DEFAULT.into(),
+                                ..default()
+                            })
+                            .insert(Button)
+                            .insert(Interaction::default())
+                            .insert(ImageSelectValue(key.clone()))
+                            .id()
+                    })
+                    .collect();
+                commands.entity(game_ui.image_select.root).despawn_descendants();
+                commands.entity(game_ui.image_select.root).push_children(select_options.as_slice());
+
+                game_control_state.overwrite_set(GameControlState::ImageSelect)
+                    .unwrap_or_else(|e| warn!("{e:?}"));
+            }
+            Steps::Input { prompt, var, default, max_len } => {
+                game_ui.input.force_show(&mut style_query);
+                let default: String = default.chars().take(max_len.unwrap_or(usize::MAX)).collect();
+                let style = TextStyle {
+                    font: asset_server.load(FONT_DIALOG),
+                    font_size: 30.0,
+                    color: Color::WHITE,
+                };
+                commands
+                    .entity(game_ui.input.prompt)
+                    .insert(Text::from_section(aerugo_state.interpolate(prompt), style.clone()));
+                commands
+                    .entity(game_ui.input.field)
+                    .insert(Text::from_section(
+                        format!("{default}_"),
+                        TextStyle { font_size: 40.0, ..style },
+                    ))
+                    .insert(InputField {
+                        var: var.clone(),
+                        value: default,
+                        max_len: *max_len,
+                    });
+
+                game_control_state.overwrite_set(GameControlState::Input)
+                    .unwrap_or_else(|e| warn!("{e:?}"));
             }
             _ => {}
         }
@@ -606,6 +1345,167 @@ pub fn step_init(
     }
 }
 
+pub fn new_music_listener(
+    mut audio_channels: ResMut<AudioChannels>,
+    mut new_music_event: EventReader<NewMusicEvent>,
+    mut asset_server: CachedAssetServer,
+    audio: Res<Audio>,
+    audio_sinks: Res<Assets<AudioSink>>,
+)
+{
+    for event in new_music_event.iter() {
+        let cmd: &MusicCommand = &event.0;
+        // restored state repeats the playing loop, keep it going
+        let playing = |music: &String, is_loop: &bool| {
+            *is_loop && audio_channels.music.as_ref().map(|(current, _)| { current }) == Some(music)
+        };
+        match cmd {
+            MusicCommand::Play { music, is_loop } => {
+                if playing(music, is_loop) {
+                    continue;
+                }
+                audio_channels.stop_music(&audio_sinks);
+                let settings = if *is_loop { PlaybackSettings::LOOP } else { PlaybackSettings::ONCE };
+                let sink = audio.play_with_settings(asset_server.load(music), settings);
+                audio_channels.music = Some((music.clone(), audio_sinks.get_handle(sink)));
+            }
+            MusicCommand::Crossfade { music, is_loop, duration } => {
+                if playing(music, is_loop) {
+                    continue;
+                }
+                let duration = duration.max(0.0);
+                let settings = if *is_loop { PlaybackSettings::LOOP } else { PlaybackSettings::ONCE };
+                if duration == 0.0 {
+                    // nothing to fade, switch right away
+                    audio_channels.stop_music(&audio_sinks);
+                    let sink = audio.play_with_settings(asset_server.load(music), settings);
+                    audio_channels.music = Some((music.clone(), audio_sinks.get_handle(sink)));
+                    continue;
+                }
+                if let Some((_, sink)) = audio_channels.music.take() {
+                    audio_channels.fades.push(AudioFade {
+                        sink,
+                        timer: Timer::from_seconds(duration, TimerMode::Once),
+                        fade_in: false,
+                    });
+                }
+                let sink = audio.play_with_settings(asset_server.load(music), settings.with_volume(0.0));
+                let sink = audio_sinks.get_handle(sink);
+                audio_channels.fades.push(AudioFade {
+                    sink: sink.clone(),
+                    timer: Timer::from_seconds(duration, TimerMode::Once),
+                    fade_in: true,
+                });
+                audio_channels.music = Some((music.clone(), sink));
+            }
+            MusicCommand::Stop => {
+                audio_channels.stop_music(&audio_sinks);
+            }
+            MusicCommand::None => {}
+        }
+    }
+}
+
+pub fn new_sound_listener(
+    mut new_sound_event: EventReader<NewSoundEvent>,
+    mut asset_server: CachedAssetServer,
+    audio: Res<Audio>,
+)
+{
+    for event in new_sound_event.iter() {
+        audio.play(asset_server.load(&event.0));
+    }
+}
+
+pub fn fade_music(
+    time: Res<Time>,
+    mut audio_channels: ResMut<AudioChannels>,
+    audio_sinks: Res<Assets<AudioSink>>,
+)
+{
+    audio_channels.fades.retain_mut(|fade| {
+        // sink appears once the source is loaded
+        let sink = match audio_sinks.get(&fade.sink) {
+            None => { return true; }
+            Some(sink) => { sink }
+        };
+        fade.timer.tick(time.delta());
+        let progress = fade.timer.percent();
+        if fade.fade_in {
+            sink.set_volume(progress);
+        } else {
+            sink.set_volume(1.0 - progress);
+            if fade.timer.finished() {
+                sink.stop();
+            }
+        }
+        !fade.timer.finished()
+    });
+}
+
+pub fn stop_audio(
+    mut audio_channels: ResMut<AudioChannels>,
+    audio_sinks: Res<Assets<AudioSink>>,
+)
+{
+    audio_channels.stop_music(&audio_sinks);
+    audio_channels.stop_voice(&audio_sinks);
+}
+
+/// Copies of previous backgrounds are not part of [`GameUI`], drop them with the game
+pub fn stop_background_animation(
+    mut commands: Commands,
+    game_ui: Option<Res<GameUI>>,
+    animate_query: Query<(Entity, &AnimateBackground)>,
+    shake_query: Query<Entity, With<ShakeBackground>>,
+    mut background_query: Query<(&mut Sprite, &mut Transform)>,
+)
+{
+    if let Some((mut sprite, mut transform)) = game_ui
+        .and_then(|game_ui| { background_query.get_mut(game_ui.background).ok() })
+    {
+        sprite.color.set_a(1.0);
+        transform.translation.x = 0.0;
+        transform.translation.y = 0.0;
+        transform.scale = Vec3::ONE;
+    }
+    for (entity, animate) in animate_query.iter() {
+        if animate.despawn {
+            commands.entity(entity).despawn_recursive();
+        } else {
+            commands.entity(entity).remove::<AnimateBackground>();
+        }
+    }
+    for entity in shake_query.iter() {
+        commands.entity(entity).remove::<ShakeBackground>();
+    }
+}
+
+/// Drops zoom, pan and overlays left by [`ScreenCommand`]
+pub fn reset_screen(
+    mut commands: Commands,
+    game_ui: Option<Res<GameUI>>,
+    mut camera_query: Query<(Entity, &mut Transform, &mut OrthographicProjection), With<Camera2d>>,
+    mut color_query: Query<&mut BackgroundColor>,
+)
+{
+    for (entity, mut transform, mut projection) in camera_query.iter_mut() {
+        commands.entity(entity).remove::<AnimateCamera>();
+        transform.translation.x = 0.0;
+        transform.translation.y = 0.0;
+        projection.scale = 1.0;
+    }
+    if let Some(game_ui) = game_ui {
+        let screen = &game_ui.screen;
+        for entity in [screen.fade, screen.flash, screen.vignette] {
+            commands.entity(entity).remove::<AnimateOverlay>();
+            if let Ok(mut color) = color_query.get_mut(entity) {
+                color.0 = TRANSPARENT;
+            }
+        }
+    }
+}
+
 pub fn input_text_pass(
     mut game_control_state: ResMut<State<GameControlState>>,
     mut key_input: ResMut<Input<KeyCode>>,
@@ -628,6 +1528,7 @@ pub fn input_text_next(
     mut key_input: ResMut<Input<KeyCode>>,
     mut mouse_button_input: ResMut<Input<MouseButton>>,
     mut next_step_event: EventWriter<NextStepEvent>,
+    mut text_query: Query<&mut AnimateText>,
 )
 {
     let span = span!(Level::WARN, "input_text_next");
@@ -636,16 +1537,56 @@ pub fn input_text_next(
     if key_input.clear_just_pressed(KeyCode::Space)
         || key_input.clear_just_pressed(KeyCode::Return)
         || mouse_button_input.clear_just_pressed(MouseButton::Left) {
-        game_control_state.set(GameControlState::None).unwrap_or_else(|e| warn!("{e:?}"));
-        next_step_event.send(NextStepEvent);
+        match text_query.iter_mut().find(|animate| { animate.is_paused }) {
+            Some(mut animate) => {
+                animate.resume();
+                game_control_state.set(GameControlState::TextPass)
+                    .unwrap_or_else(|e| warn!("{e:?}"));
+            }
+            _ => {
+                game_control_state.set(GameControlState::None)
+                    .unwrap_or_else(|e| warn!("{e:?}"));
+                next_step_event.send(NextStepEvent);
+            }
+        }
+    }
+}
+
+pub fn input_rollback(
+    mut commands: Commands,
+    aerugo_state: Res<AerugoState>,
+    aerugo: Res<Aerugo>,
+    mut game_state: ResMut<State<GameState>>,
+    mut key_input: ResMut<Input<KeyCode>>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+)
+{
+    let span = span!(Level::WARN, "input_rollback");
+    let _enter = span.enter();
+
+    let scrolled_up = mouse_wheel.iter().filter(|e| { e.y > 0.0 }).count() > 0;
+    if key_input.clear_just_pressed(KeyCode::PageUp) || scrolled_up {
+        let mut state = aerugo_state.clone();
+        match state.rollback(1, &aerugo) {
+            Ok(0) => {}
+            Ok(_) => {
+                game_state.set(GameState::Init)
+                    .unwrap_or_else(|e| warn!("{e:?}"));
+                commands.insert_resource(AerugoLoaded(state.reload()));
+            }
+            Err(e) => { warn!("{e:?}"); }
+        }
     }
 }
 
 pub fn input_phrase(
+    mut commands: Commands,
     mut aerugo_state: ResMut<AerugoState>,
     aerugo: Res<Aerugo>,
     mut game_control_state: ResMut<State<GameControlState>>,
     mut phrase_query: Query<(&Interaction, &PhraseValue, &mut BackgroundColor), Changed<Interaction>>,
+    mut timer_query: Query<(&mut Style, &mut PhraseTimer)>,
+    time: Res<Time>,
     mut next_step_event: EventWriter<NextStepEvent>,
 )
 {
@@ -660,11 +1601,18 @@ pub fn input_phrase(
             Interaction::Clicked => {
                 *color = TRANSPARENT.into();
 
-                let step = aerugo_state.step(aerugo.as_ref());
+                let step = match aerugo_state.step(aerugo.as_ref()) {
+                    Ok(step) => { step }
+                    Err(error) => {
+                        commands.insert_resource(AerugoFailure { error, fatal: false });
+                        return;
+                    }
+                };
                 aerugo_state.select_unique(step.id, phrase.0.clone());
                 game_control_state.set(GameControlState::None)
                     .unwrap_or_else(|e| warn!("{e:?}"));
                 next_step_event.send(NextStepEvent);
+                return;
             }
             Interaction::Hovered => {
                 *color = Color::rgba(1.0, 1.0, 1.0, 0.1).into();
@@ -674,6 +1622,121 @@ pub fn input_phrase(
             }
         }
     }
+
+    // runs only in `GameControlState::Phrase`, so pause stops the countdown
+    for (style, timer) in timer_query.iter_mut() {
+        let mut style: Mut<Style> = style;
+        let mut timer: Mut<PhraseTimer> = timer;
+        timer.timer.tick(time.delta());
+        style.size.width = Val::Percent(timer.width * timer.timer.percent_left());
+        if timer.timer.just_finished() {
+            let step = match aerugo_state.step(aerugo.as_ref()) {
+                Ok(step) => { step }
+                Err(error) => {
+                    commands.insert_resource(AerugoFailure { error, fatal: false });
+                    return;
+                }
+            };
+            let available = match &step.inner {
+                Steps::Phrase { phrases, .. } => {
+                    phrases.iter().any(|option| {
+                        option.key == timer.default && option.is_available(&aerugo_state)
+                    })
+                }
+                _ => { false }
+            };
+            if !available {
+                continue;
+            }
+            aerugo_state.select_unique(step.id, timer.default.clone());
+            game_control_state.set(GameControlState::None)
+                .unwrap_or_else(|e| warn!("{e:?}"));
+            next_step_event.send(NextStepEvent);
+        }
+    }
+}
+
+pub fn input_image_select(
+    mut commands: Commands,
+    mut aerugo_state: ResMut<AerugoState>,
+    aerugo: Res<Aerugo>,
+    mut game_control_state: ResMut<State<GameControlState>>,
+    mut option_query: Query<
+        (&Interaction, &ImageSelectValue, &mut BackgroundColor),
+        Changed<Interaction>
+    >,
+    mut next_step_event: EventWriter<NextStepEvent>,
+)
+{
+    let span = span!(Level::WARN, "input_image_select");
+    let _enter = span.enter();
+
+    for (interaction, value, mut color) in option_query.iter_mut() {
+        match interaction {
+            Interaction::Clicked => {
+                let step = match aerugo_state.step(aerugo.as_ref()) {
+                    Ok(step) => { step }
+                    Err(error) => {
+                        commands.insert_resource(AerugoFailure { error, fatal: false });
+                        return;
+                    }
+                };
+                aerugo_state.select_unique(step.id, value.0.clone());
+                game_control_state.set(GameControlState::None)
+                    .unwrap_or_else(|e| warn!("{e:?}"));
+                next_step_event.send(NextStepEvent);
+            }
+            Interaction::Hovered => {
+                *color = ImageSelectUI::HOVERED.into();
+            }
+            Interaction::None => {
+                *color = ImageSelectUI::DEFAULT.into();
+            }
+        }
+    }
+}
+
+pub fn input_text_field(
+    mut commands: Commands,
+    mut aerugo_state: ResMut<AerugoState>,
+    mut game_control_state: ResMut<State<GameControlState>>,
+    mut key_input: ResMut<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut field_query: Query<(Entity, &mut Text, &mut InputField)>,
+    mut next_step_event: EventWriter<NextStepEvent>,
+)
+{
+    let span = span!(Level::WARN, "input_text_field");
+    let _enter = span.enter();
+
+    for (entity, text, field) in field_query.iter_mut() {
+        let mut text: Mut<Text> = text;
+        let mut field: Mut<InputField> = field;
+
+        // skip keys which were pressed before the field came
+        let is_new = field.is_added();
+        for event in characters.iter() {
+            let is_full = field.max_len
+                .is_some_and(|max_len| { field.value.chars().count() >= max_len });
+            // backspace and enter come as control characters too
+            if !is_new && !event.char.is_control() && !is_full {
+                field.value.push(event.char);
+            }
+        }
+        if key_input.clear_just_pressed(KeyCode::Back) {
+            field.value.pop();
+        }
+        text.sections[0].value = format!("{}_", field.value);
+
+        let value = field.value.trim();
+        if key_input.clear_just_pressed(KeyCode::Return) && !value.is_empty() {
+            aerugo_state.set_input(field.var.clone(), value.to_string());
+            commands.entity(entity).remove::<InputField>();
+            game_control_state.set(GameControlState::None)
+                .unwrap_or_else(|e| warn!("{e:?}"));
+            next_step_event.send(NextStepEvent);
+        }
+    }
 }
 
 pub fn animate(
@@ -681,10 +1744,30 @@ pub fn animate(
     time: Res<Time>,
     mut game_ui: ResMut<GameUI>,
     mut game_control_state: ResMut<State<GameControlState>>,
+    mut next_step_event: EventWriter<NextStepEvent>,
     window: Res<Windows>,
-    mut text_query: Query<(&mut Text, &mut AnimateText)>,
+    mut text_query: Query<(Entity, &mut Text, &mut AnimateText)>,
     mut sprite_fade_query: Query<(&mut Sprite, &mut AnimateFadeSprite)>,
     mut sprite_move_query: Query<(&mut Transform, &mut AnimateMoveSprite)>,
+    mut sprite_transform_query: Query<
+        (Entity, &mut Transform, &mut Sprite, &mut AnimateTransformSprite),
+        (Without<AnimateFadeSprite>, Without<AnimateMoveSprite>),
+    >,
+    mut background_query: Query<
+        (
+            Entity,
+            &mut Sprite,
+            &mut Transform,
+            Option<&mut AnimateBackground>,
+            Option<&mut ShakeBackground>,
+        ),
+        (
+            Or<(With<AnimateBackground>, With<ShakeBackground>)>,
+            Without<AnimateFadeSprite>,
+            Without<AnimateMoveSprite>,
+            Without<AnimateTransformSprite>,
+        ),
+    >,
     texture_atlases: Res<Assets<TextureAtlas>>,
     mut scene_query: Query<(
         Entity,
@@ -692,36 +1775,38 @@ pub fn animate(
         &mut TextureAtlasSprite,
         &Handle<TextureAtlas>,
     )>,
+    mut camera_query: Query<
+        (Entity, &mut Transform, &mut OrthographicProjection, &mut AnimateCamera),
+        (Without<Sprite>, Without<AnimateMoveSprite>),
+    >,
+    mut overlay_query: Query<(Entity, &mut BackgroundColor, &mut AnimateOverlay)>,
 )
 {
     let span = span!(Level::WARN, "animate");
     let _enter = span.enter();
 
     let mut unmute_control = true;
+    let mut auto_next: Option<Entity> = None;
     let pass = game_control_state.current().eq(&GameControlState::Text);
 
     let window = window.get_primary().unwrap();
     let w = window.width() / 2.0;
     // let h = window.height();
 
-    let text_animate = text_query.get_mut(game_ui.text.text);
-    if let Ok((text, animate)) = text_animate {
-        unmute_control = false;
-
+    for (entity, text, animate) in text_query.iter_mut() {
         let mut text: Mut<Text> = text;
         let mut animate: Mut<AnimateText> = animate;
 
-        if pass {
-            animate.chars = animate.text.chars().count();
-        } else if animate.timer.tick(time.delta()).just_finished() {
-            animate.chars += 1;
+        if animate.advance(time.delta(), pass) {
+            unmute_control = false;
         }
-        text.sections = vec![TextSection {
-            value: animate.text.substring(0, animate.chars).to_string(),
-            style: animate.style.clone(),
-        }];
-        if animate.text.chars().count() <= animate.chars {
-            commands.entity(game_ui.text.text).remove::<AnimateText>();
+        animate.elapsed += time.delta_seconds();
+        text.sections = text_sections(&animate);
+        let is_typed = animate.total() <= animate.chars;
+        if is_typed && animate.auto_next {
+            auto_next = Some(entity);
+        } else if is_typed && !animate.has_effects() {
+            commands.entity(entity).remove::<AnimateText>();
         }
     }
 
@@ -729,14 +1814,18 @@ pub fn animate(
         let (mut sprite, mut animate_fade): (Mut<Sprite>, Mut<AnimateFadeSprite>) = sprite;
         unmute_control = false;
 
+        if !animate_fade.alpha.is_finite() {
+            animate_fade.alpha = sprite.color.a();
+        }
         animate_fade.timer.tick(time.delta());
 
+        let percent = animate_fade.easing.apply(animate_fade.timer.percent());
         let alfa = if animate_fade.fade_in {
-            animate_fade.timer.percent()
+            percent
         } else {
-            1.0 - animate_fade.timer.percent()
+            1.0 - percent
         };
-        sprite.color.set_a(alfa);
+        sprite.color.set_a(alfa * animate_fade.alpha);
         if animate_fade.timer.just_finished() {
             if animate_fade.fade_in {
                 commands.entity(*game_ui.sprites.get(&animate_fade.name).unwrap())
@@ -766,8 +1855,9 @@ pub fn animate(
 
         animate_move.timer.tick(time.delta());
 
+        let percent = animate_move.easing.apply(animate_move.timer.percent());
         transform.translation.x = animate_move.start_pos
-            + (end_pos - animate_move.start_pos) * animate_move.timer.percent();
+            + (end_pos - animate_move.start_pos) * percent;
 
         if animate_move.timer.just_finished() {
             if !animate_move.move_out {
@@ -778,28 +1868,366 @@ pub fn animate(
         }
     }
 
+    for (entity, mut transform, mut sprite, mut animate) in sprite_transform_query.iter_mut() {
+        unmute_control = false;
+
+        let (start_y, start_scale, start_color) = *animate.start.get_or_insert((
+            transform.translation.y,
+            transform.scale.x,
+            sprite.color,
+        ));
+        let (end_y, end_scale, end_color) = animate.end;
+        animate.timer.tick(time.delta());
+        let percent = animate.easing.apply(animate.timer.percent());
+
+        let lerp = |from: f32, to: f32| { from + (to - from) * percent };
+        transform.translation.y = lerp(start_y, end_y);
+        let scale = lerp(start_scale, end_scale);
+        transform.scale = Vec3::new(scale, scale, 1.0);
+        sprite.color = lerp_color(start_color, end_color, percent);
+        if animate.timer.finished() {
+            commands.entity(entity).remove::<AnimateTransformSprite>();
+        }
+    }
+
+    for (entity, sprite, transform, animate, shake) in background_query.iter_mut() {
+        let mut sprite: Mut<Sprite> = sprite;
+        let mut transform: Mut<Transform> = transform;
+        unmute_control = false;
+
+        let mut x = 0.0;
+        if let Some(mut animate) = animate {
+            animate.timer.tick(time.delta());
+            let (start, end) = animate.span;
+            let percent = animate.easing.apply((animate.timer.percent() - start) / (end - start));
+            let (a0, a1) = animate.alpha;
+            let (x0, x1) = animate.offset;
+            sprite.color.set_a(a0 + (a1 - a0) * percent);
+            x = (x0 + (x1 - x0) * percent) * w * 2.0;
+            if animate.timer.finished() {
+                if animate.despawn {
+                    commands.entity(entity).despawn_recursive();
+                } else {
+                    commands.entity(entity).remove::<AnimateBackground>();
+                }
+            }
+        }
+        let mut y = 0.0;
+        let mut scale = 1.0;
+        if let Some(mut shake) = shake {
+            const FREQUENCY: f32 = 15.0;
+            shake.timer.tick(time.delta());
+            let fade = 1.0 - Easing::Linear.apply(shake.timer.percent());
+            let phase = shake.timer.elapsed_secs() * FREQUENCY * std::f32::consts::TAU;
+            let amplitude = shake.strength * w * 2.0 * fade;
+            x += amplitude * phase.sin();
+            y = amplitude * (phase * 1.3).cos();
+            // hide edges moved into view
+            scale = 1.0 + shake.strength * 2.0 * fade;
+            if shake.timer.finished() {
+                commands.entity(entity).remove::<ShakeBackground>();
+                y = 0.0;
+                scale = 1.0;
+            }
+        }
+        transform.translation.x = x;
+        transform.translation.y = y;
+        transform.scale = Vec3::new(scale, scale, 1.0);
+    }
+
     for (entity, animate, sprite, texture) in scene_query.iter_mut() {
         let mut animate: Mut<AnimateScene> = animate;
         let mut sprite: Mut<TextureAtlasSprite> = sprite;
         let texture: &Handle<TextureAtlas> = texture;
 
         if animate.is_paused { continue; }
+        if animate.wait && !animate.is_loop {
+            unmute_control = false;
+        }
         animate.timer.tick(time.delta());
-        if animate.timer.just_finished() {
-            let texture_atlas = texture_atlases.get(texture).unwrap();
-            if !animate.is_loop && sprite.index + 1 == texture_atlas.textures.len() {
-                commands.entity(entity).remove::<AnimateScene>();
+        let len = texture_atlases.get(texture).map_or(1, |t| t.textures.len());
+        let last = animate.frames.1.min(len.max(1) - 1);
+        let first = animate.frames.0.min(last);
+        let count = last - first + 1;
+        // eased share of the pass, last frame keeps its own slot
+        let passed = (animate.easing.apply(animate.timer.percent()) * count as f32) as usize;
+        let offset = passed.min(count - 1);
+        sprite.index = if animate.is_backward { last - offset } else { first + offset };
+        if animate.timer.finished() {
+            if animate.ping_pong && !animate.is_backward && last > first {
+                animate.is_backward = true;
+                animate.timer.reset();
+            } else if animate.is_loop {
+                animate.is_backward = false;
+                animate.timer.reset();
             } else {
-                sprite.index = (sprite.index + 1) % texture_atlas.textures.len();
+                commands.entity(entity).remove::<AnimateScene>();
             }
         }
     }
 
-    if unmute_control && game_control_state.current().eq(&GameControlState::TextPass) {
+    for (entity, mut transform, mut projection, mut animate) in camera_query.iter_mut() {
+        unmute_control = false;
+
+        let (start_xy, start_scale) = *animate.start.get_or_insert((
+            transform.translation.truncate(),
+            projection.scale,
+        ));
+        let (end_xy, end_scale) = animate.end;
+        animate.timer.tick(time.delta());
+        let percent = animate.easing.apply(animate.timer.percent());
+
+        let xy = start_xy.lerp(end_xy, percent);
+        transform.translation.x = xy.x;
+        transform.translation.y = xy.y;
+        projection.scale = start_scale + (end_scale - start_scale) * percent;
+        if animate.timer.finished() {
+            commands.entity(entity).remove::<AnimateCamera>();
+        }
+    }
+
+    for (entity, mut color, mut animate) in overlay_query.iter_mut() {
+        unmute_control = false;
+
+        let start = *animate.start.get_or_insert(color.0);
+        animate.timer.tick(time.delta());
+        let percent = animate.easing.apply(animate.timer.percent());
+
+        color.0 = lerp_color(start, animate.end, percent);
+        if animate.timer.finished() {
+            commands.entity(entity).remove::<AnimateOverlay>();
+        }
+    }
+
+    let is_text = matches!(
+        game_control_state.current(),
+        GameControlState::TextPass | GameControlState::Text
+    );
+    if let (true, Some(entity), true) = (unmute_control, auto_next, is_text) {
+        commands.entity(entity).remove::<AnimateText>();
+        game_control_state.set(GameControlState::None).unwrap_or_else(|e| warn!("{e:?}"));
+        next_step_event.send(NextStepEvent);
+    } else if unmute_control && game_control_state.current().eq(&GameControlState::TextPass) {
         game_control_state.set(GameControlState::Text).unwrap_or_else(|e| warn!("{e:?}"));
     }
 }
 
+pub fn animate_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut game_ui: ResMut<GameUI>,
+    mut emitter_query: Query<(Entity, &mut ParticleEmitter, Option<&Children>)>,
+    mut particle_query: Query<(Entity, &mut Transform, &mut Sprite, &mut Particle)>,
+    window: Res<Windows>,
+)
+{
+    let span = span!(Level::WARN, "animate_particles");
+    let _enter = span.enter();
+
+    const LIMIT: usize = 1500;
+
+    let window = window.get_primary().unwrap();
+    let w = window.width() / 2.0;
+    let h = window.height() / 2.0;
+    let delta = time.delta_seconds();
+
+    for (entity, mut emitter, children) in emitter_query.iter_mut() {
+        let mut count = children.map_or(0, |children| { children.len() });
+        if emitter.is_stopped {
+            if count == 0 {
+                commands.entity(entity).despawn_recursive();
+                game_ui.particles.retain(|_, e| { *e != entity });
+            }
+            continue;
+        }
+
+        let (rate, size, color) = particle_style(emitter.kind);
+        // no more than a screen full per frame, whatever the intensity
+        emitter.pending = (emitter.pending + rate * emitter.intensity.max(0.0) * delta)
+            .min(LIMIT as f32);
+        while emitter.pending >= 1.0 {
+            emitter.pending -= 1.0;
+            if count >= LIMIT { continue; }
+            count += 1;
+
+            let (position, particle) = new_particle(&mut emitter, w, h);
+            let mut transform = Transform::from_translation(position.extend(0.0));
+            if particle.kind == ParticleKind::Rain {
+                let velocity = particle.velocity;
+                transform.rotation = Quat::from_rotation_z(velocity.x.atan2(-velocity.y));
+            }
+            let sprite = Sprite {
+                color: match emitter.texture {
+                    None => { color }
+                    Some(_) => { Color::rgba(1.0, 1.0, 1.0, color.a()) }
+                },
+                custom_size: Some(size),
+                ..default()
+            };
+            commands.entity(entity).with_children(|parent| {
+                parent
+                    .spawn(SpriteBundle {
+                        sprite,
+                        transform,
+                        texture: emitter.texture.clone().unwrap_or_default(),
+                        ..default()
+                    })
+                    .insert(particle);
+            });
+        }
+    }
+
+    for (entity, mut transform, mut sprite, mut particle) in particle_query.iter_mut() {
+        particle.age += delta;
+        let (sway, phase) = particle.sway;
+        let x = particle.velocity.x + sway * (particle.age * 2.0 + phase).cos();
+        transform.translation.x += x * delta;
+        transform.translation.y += particle.velocity.y * delta;
+        transform.rotate_z(particle.spin * delta);
+        if particle.life.is_finite() {
+            let fade = (particle.age / particle.life * std::f32::consts::PI).sin();
+            sprite.color.set_a(particle.alpha * fade.max(0.0));
+        }
+
+        let position = transform.translation;
+        if particle.age > particle.life || position.y < -h - 40.0 || position.x.abs() > w * 1.6 {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Particles per second at intensity 1, size and color of one
+fn particle_style(kind: ParticleKind) -> (f32, Vec2, Color) {
+    match kind {
+        ParticleKind::Rain => { (300.0, Vec2::new(2.0, 24.0), Color::rgba(0.7, 0.8, 1.0, 0.5)) }
+        ParticleKind::Snow => { (80.0, Vec2::new(6.0, 6.0), Color::rgba(1.0, 1.0, 1.0, 0.9)) }
+        ParticleKind::Dust => { (30.0, Vec2::new(3.0, 3.0), Color::rgba(0.9, 0.85, 0.7, 0.6)) }
+        ParticleKind::Petals => { (15.0, Vec2::new(14.0, 9.0), Color::rgba(1.0, 0.75, 0.8, 1.0)) }
+    }
+}
+
+/// Position and motion of a new particle, `w` and `h` are half of window
+fn new_particle(emitter: &mut ParticleEmitter, w: f32, h: f32) -> (Vec2, Particle) {
+    use std::f32::consts::TAU;
+
+    let (_, _, color) = particle_style(emitter.kind);
+    let wind = emitter.wind;
+    // wider than window, wind brings particles from aside
+    let x = emitter.random(-w * 1.5, w * 1.5);
+    let top = h + 30.0;
+    let mut particle = Particle {
+        kind: emitter.kind,
+        velocity: Vec2::ZERO,
+        spin: 0.0,
+        sway: (0.0, 0.0),
+        alpha: color.a(),
+        age: 0.0,
+        life: f32::INFINITY,
+    };
+    let position = match emitter.kind {
+        ParticleKind::Rain => {
+            particle.velocity = Vec2::new(wind * 300.0, -emitter.random(900.0, 1300.0));
+            Vec2::new(x, top)
+        }
+        ParticleKind::Snow => {
+            particle.velocity = Vec2::new(wind * 60.0, -emitter.random(40.0, 90.0));
+            particle.sway = (emitter.random(10.0, 30.0), emitter.random(0.0, TAU));
+            Vec2::new(x, top)
+        }
+        ParticleKind::Dust => {
+            particle.velocity = Vec2::new(
+                emitter.random(-15.0, 15.0) + wind * 20.0,
+                emitter.random(-10.0, 10.0),
+            );
+            particle.life = emitter.random(3.0, 6.0);
+            Vec2::new(emitter.random(-w, w), emitter.random(-h, h))
+        }
+        ParticleKind::Petals => {
+            particle.velocity = Vec2::new(wind * 80.0 + 20.0, -emitter.random(60.0, 110.0));
+            particle.spin = emitter.random(-3.0, 3.0);
+            particle.sway = (emitter.random(20.0, 50.0), emitter.random(0.0, TAU));
+            Vec2::new(x, top)
+        }
+    };
+    (position, particle)
+}
+
+/// Bold author before text on NVL page, nothing for narrator
+fn author_section(author: &str, style: &TextStyle, fonts: &FlowFonts) -> Option<TextSection> {
+    match author.is_empty() {
+        true => { None }
+        false => {
+            Some(TextSection {
+                value: format!("{author}: "),
+                style: TextStyle { font: fonts.bold.clone(), ..style.clone() },
+            })
+        }
+    }
+}
+
+/// Shown part of text, a section for every piece and for every character with effect
+fn text_sections(animate: &AnimateText) -> Vec<TextSection> {
+    const RUBY_SIZE: f32 = 0.5;
+
+    let mut sections = animate.page.clone();
+    let mut left = animate.chars;
+    for (i, piece) in animate.pieces.iter().enumerate() {
+        if left == 0 { break; }
+        let count = piece.text.chars().count();
+        let shown = piece.text.substring(0, left);
+        left = left.saturating_sub(count);
+
+        let mark = &piece.mark;
+        let style = TextStyle {
+            font: match (mark.bold, mark.italic) {
+                (false, false) => { animate.style.font.clone() }
+                (true, false) => { animate.fonts.bold.clone() }
+                (false, true) => { animate.fonts.italic.clone() }
+                (true, true) => { animate.fonts.bold_italic.clone() }
+            },
+            font_size: animate.style.font_size * mark.size.unwrap_or(1.0),
+            color: mark.color
+                .map(|[r, g, b, a]| { Color::rgba(r, g, b, a) })
+                .unwrap_or(animate.style.color),
+        };
+        match mark.effect {
+            None => {
+                sections.push(TextSection { value: shown.to_string(), style: style.clone() });
+            }
+            Some(effect) => {
+                // bevy text can not move glyphs, effects play with their size
+                for (j, c) in shown.chars().enumerate() {
+                    let phase = (i * 31 + j) as f32;
+                    let scale = match effect {
+                        TextEffect::Wave => {
+                            1.0 + 0.15 * (animate.elapsed * 6.0 - phase * 0.6).sin()
+                        }
+                        TextEffect::Shake => {
+                            // hash of frame and character, changes 20 times a second
+                            let tick = (animate.elapsed * 20.0).floor();
+                            let noise = (tick * 12.9898 + phase * 78.233).sin() * 43758.547;
+                            1.0 + 0.1 * (noise.rem_euclid(1.0) * 2.0 - 1.0)
+                        }
+                    };
+                    sections.push(TextSection {
+                        value: c.to_string(),
+                        style: TextStyle { font_size: style.font_size * scale, ..style.clone() },
+                    });
+                }
+            }
+        }
+        if let (Some(ruby), true) = (&piece.ruby, shown.chars().count() == count) {
+            let mut color = style.color;
+            color.set_a(color.a() * 0.7);
+            sections.push(TextSection {
+                value: format!("({ruby})"),
+                style: TextStyle { font_size: style.font_size * RUBY_SIZE, color, ..style },
+            });
+        }
+    }
+    sections
+}
+
 pub fn show_game(
     game_ui: Res<GameUI>,
     query: Query<&mut Style>,
@@ -833,6 +2261,33 @@ pub fn disable_game_input(mut state: ResMut<State<GameControlState>>)
     state.push(GameControlState::None).unwrap_or_else(|e| warn!("{e:?}"));
 }
 
-pub fn force_game_ui(mut state: ResMut<State<UiState>>) {
-    let _ = state.overwrite_set(UiState::Game);
+pub fn force_game_ui(
+    mut state: ResMut<State<UiState>>,
+    failure: Option<Res<AerugoFailure>>,
+)
+{
+    if failure.is_none() {
+        let _ = state.overwrite_set(UiState::Game);
+    }
+}
+
+pub fn aerugo_failure_listener(
+    failure: Option<Res<AerugoFailure>>,
+    mut game_state: ResMut<State<GameState>>,
+    mut ui_state: ResMut<State<UiState>>,
+)
+{
+    if let Some(failure) = failure {
+        if failure.is_added() {
+            error!("{}", failure.error);
+        }
+        if !game_state.current().eq(&GameState::None) {
+            game_state.overwrite_set(GameState::None)
+                .unwrap_or_else(|e| warn!("{e:?}"));
+        }
+        if !ui_state.current().eq(&UiState::Error) {
+            ui_state.overwrite_set(UiState::Error)
+                .unwrap_or_else(|e| warn!("{e:?}"));
+        }
+    }
 }
@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use aerugo::{Aerugo, AerugoState};
 
 use crate::game::GameState;
+use crate::ui::GameUI;
 
 pub struct SavePlugin;
 
@@ -54,7 +55,7 @@ pub fn pre_load_saves(aerugo: &Aerugo) -> Saves
             let mut save_data = String::new();
             save.read_to_string(&mut save_data).unwrap();
             if let Ok(save) = ron::from_str::<Save>(&save_data) {
-                if AerugoState::validate(&aerugo, &save.state).is_some() {
+                if AerugoState::validate(&aerugo, &save.state).is_ok() {
                     saves.insert(n, save);
                 }
             }
@@ -66,7 +67,8 @@ pub fn pre_load_saves(aerugo: &Aerugo) -> Saves
 pub fn save(world: &mut World) {
     let save_mark = world.remove_resource::<SaveMark>();
     if let Some(save_mark) = save_mark {
-        let aerugo_state = world.get_resource::<AerugoState>().unwrap().clone();
+        let mut aerugo_state = world.get_resource::<AerugoState>().unwrap().clone();
+        aerugo_state.inspector.scene_frame = scene_frame(world);
         let save = Save { state: aerugo_state, timestamp: chrono::Local::now() };
         _save(
             format!("save{}.ron", save_mark.to),
@@ -79,6 +81,12 @@ pub fn save(world: &mut World) {
     }
 }
 
+/// Shown frame of scene animation
+fn scene_frame(world: &World) -> Option<usize> {
+    let scene = world.get_resource::<GameUI>()?.scene;
+    world.get::<TextureAtlasSprite>(scene).map(|sprite| sprite.index)
+}
+
 fn _save(save_path: String, data: String) {
     let path = std::env::current_dir().unwrap()
         .join("saves");
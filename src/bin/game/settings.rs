@@ -15,6 +15,9 @@ pub struct Settings {
     pub flow_speed: f32,
     #[serde(default = "Defs::flow_size")]
     pub flow_size: f32,
+    /// Countdown of timed phrases, off waits for player as usual
+    #[serde(default = "Defs::choice_timers")]
+    pub choice_timers: bool,
 }
 
 struct Defs;
@@ -23,6 +26,7 @@ impl Defs {
     fn narrator_size() -> f32 { 31.0 }
     fn flow_speed() -> f32 { 0.02 }
     fn flow_size() -> f32 { 31.0 }
+    fn choice_timers() -> bool { true }
 }
 
 impl Default for Settings {
@@ -33,6 +37,7 @@ impl Default for Settings {
             narrator_size: Defs::narrator_size(),
             flow_speed: Defs::flow_speed(),
             flow_size: Defs::flow_size(),
+            choice_timers: Defs::choice_timers(),
         }
     }
 }
@@ -10,6 +10,7 @@ pub fn spawn(
     mut asset_server: CachedAssetServer,
     saves: Res<Saves>,
     window: Res<Windows>,
+    mut images: ResMut<Assets<Image>>,
 )
 {
     let main_menu = spawn_main_menu::spawn(&mut commands, &mut asset_server);
@@ -23,7 +24,9 @@ pub fn spawn(
     );
     commands.insert_resource(save);
 
-    let game = spawn_game(&mut commands, &mut asset_server, window.as_ref());
+    let game = spawn_game(
+        &mut commands, &mut asset_server, window.as_ref(), images.as_mut(),
+    );
     commands.insert_resource(game);
 
     let game_menu = spawn_game_menu::spawn(&mut commands, &mut asset_server);
@@ -1,4 +1,6 @@
 use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::ui::FocusPolicy;
 use bevy::utils::HashMap;
 
 use crate::utils::*;
@@ -9,6 +11,7 @@ pub(crate) fn spawn_game(
     commands: &mut Commands,
     asset_server: &mut CachedAssetServer,
     window: &Windows,
+    images: &mut Assets<Image>,
 ) -> GameUI
 {
     let text_font = asset_server.load(FONT_DEFAULT);
@@ -19,7 +22,25 @@ pub(crate) fn spawn_game(
 
     let mut text_ui = None;
 
+    let mut ui_nvl = NvlUI {
+        root: Entity::from_raw(0),
+        is_visible: false,
+        text: Entity::from_raw(0),
+    };
+    let mut ui_input = InputUI {
+        root: Entity::from_raw(0),
+        is_visible: false,
+        prompt: Entity::from_raw(0),
+        field: Entity::from_raw(0),
+    };
     let mut ui_phrase = Entity::from_raw(0);
+    let mut ui_image_select = Entity::from_raw(0);
+    let mut ui_screen = ScreenUI {
+        fade: Entity::from_raw(0),
+        flash: Entity::from_raw(0),
+        vignette: Entity::from_raw(0),
+    };
+    let vignette = images.add(vignette_image());
 
     let root = commands
         .spawn(NodeBundle {
@@ -33,6 +54,30 @@ pub(crate) fn spawn_game(
             background_color: TRANSPARENT.into(),
             ..Default::default()
         })
+        // ScreenUI, spawned first to stay under the rest
+        .with_children(|parent| {
+            let overlay = NodeBundle {
+                style: Style {
+                    size: SIZE_ALL,
+                    position_type: PositionType::Absolute,
+                    ..Default::default()
+                },
+                background_color: TRANSPARENT.into(),
+                focus_policy: FocusPolicy::Pass,
+                ..Default::default()
+            };
+            ui_screen.vignette = parent
+                .spawn(ImageBundle {
+                    style: overlay.style.clone(),
+                    image: vignette.into(),
+                    background_color: TRANSPARENT.into(),
+                    focus_policy: FocusPolicy::Pass,
+                    ..Default::default()
+                })
+                .id();
+            ui_screen.fade = parent.spawn(overlay.clone()).id();
+            ui_screen.flash = parent.spawn(overlay).id();
+        })
         // TextUI
         .with_children(|parent| {
             text_ui = Some(spawn_text_ui(
@@ -41,6 +86,45 @@ pub(crate) fn spawn_game(
                 asset_server,
             ));
         })
+        // NvlUI
+        .with_children(|parent| {
+            ui_nvl.root = parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::None,
+                        size: SIZE_ALL,
+                        position_type: PositionType::Absolute,
+                        flex_direction: FlexDirection::Column,
+                        justify_content: JustifyContent::FlexStart,
+                        padding: UiRect::new(
+                            Val::Percent(10.0),
+                            Val::Percent(10.0),
+                            Val::Percent(5.0),
+                            Val::Percent(5.0),
+                        ),
+                        ..Default::default()
+                    },
+                    background_color: GLASS_DARK.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    ui_nvl.text = parent
+                        .spawn(TextBundle {
+                            style: Style {
+                                max_size: Size::new(Val::Px(w * 0.8), Val::Undefined),
+                                ..default()
+                            },
+                            text: Text::from_section("", TextStyle {
+                                font: text_font.clone(),
+                                font_size: 20.0,
+                                color: Color::WHITE,
+                            }),
+                            ..Default::default()
+                        })
+                        .id();
+                })
+                .id();
+        })
         // PhraseUI
         .with_children(|parent| {
             ui_phrase = parent
@@ -60,6 +144,69 @@ pub(crate) fn spawn_game(
                 })
                 .id();
         })
+        // ImageSelectUI
+        .with_children(|parent| {
+            ui_image_select = parent
+                .spawn(ImageBundle {
+                    style: Style {
+                        display: Display::None,
+                        size: SIZE_ALL,
+                        position_type: PositionType::Absolute,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .id();
+        })
+        // InputUI
+        .with_children(|parent| {
+            let text = |value: &str, font_size: f32| {
+                TextBundle {
+                    text: Text::from_section(value, TextStyle {
+                        font: text_font.clone(),
+                        font_size,
+                        color: Color::WHITE,
+                    }),
+                    style: Style {
+                        margin: UiRect::all(Val::Px(10.0)),
+                        ..default()
+                    },
+                    ..Default::default()
+                }
+            };
+            ui_input.root = parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        display: Display::None,
+                        size: SIZE_ALL,
+                        position_type: PositionType::Absolute,
+                        align_items: AlignItems::Center,
+                        justify_content: JustifyContent::Center,
+                        ..Default::default()
+                    },
+                    background_color: TRANSPARENT.into(),
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    parent
+                        .spawn(NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(50.0), Val::Auto),
+                                flex_direction: FlexDirection::Column,
+                                align_items: AlignItems::Center,
+                                padding: UiRect::all(Val::Px(20.0)),
+                                ..Default::default()
+                            },
+                            background_color: GLASS_DARK.into(),
+                            ..Default::default()
+                        })
+                        .with_children(|parent| {
+                            ui_input.prompt = parent.spawn(text("", 30.0)).id();
+                            ui_input.field = parent.spawn(text("", 40.0)).id();
+                        });
+                })
+                .id();
+        })
         .id();
 
     let background = commands
@@ -100,11 +247,36 @@ pub(crate) fn spawn_game(
         scene,
         scene_visible: false,
         sprites: Default::default(),
+        particles: Default::default(),
         text: text_ui.unwrap(),
+        nvl: ui_nvl,
         phrase: PhraseUI { root: ui_phrase, is_visible: false },
+        image_select: ImageSelectUI { root: ui_image_select, is_visible: false },
+        input: ui_input,
+        screen: ui_screen,
     }
 }
 
+/// Black edges fading to transparent center
+fn vignette_image() -> Image {
+    const SIZE: u32 = 256;
+    let data = (0..SIZE * SIZE)
+        .flat_map(|i| {
+            let x = (i % SIZE) as f32 / (SIZE - 1) as f32 * 2.0 - 1.0;
+            let y = (i / SIZE) as f32 / (SIZE - 1) as f32 * 2.0 - 1.0;
+            let distance = (x * x + y * y).sqrt() / std::f32::consts::SQRT_2;
+            let alpha = ((distance - 0.4) / 0.6).clamp(0.0, 1.0).powi(2);
+            [0, 0, 0, (alpha * 255.0) as u8]
+        })
+        .collect();
+    Image::new(
+        Extent3d { width: SIZE, height: SIZE, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
 fn spawn_text_ui(
     builder: &mut ChildBuilder,
     text_font: Handle<Font>,
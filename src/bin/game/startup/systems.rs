@@ -1,6 +1,10 @@
 use bevy::prelude::*;
-use aerugo::{Aerugo, BackgroundCommand, NarratorCommand, SceneCommand, SpriteCommand, Steps};
+use aerugo::{
+    Aerugo, BackgroundCommand, MusicCommand, NarratorCommand, ParticlesCommand, SceneCommand,
+    SpriteCommand, Steps,
+};
 use aerugo::international::Internationale;
+use crate::game::AerugoFailure;
 use crate::translator::Translator;
 use crate::settings::Settings;
 
@@ -96,11 +100,14 @@ pub fn remove_splash_screen(
     mut commands: Commands,
     mut ui_state: ResMut<State<UiState>>,
     splash_screen: Res<SplashScreen>,
+    failure: Option<Res<AerugoFailure>>,
 )
 {
     commands.entity(splash_screen.root).despawn_recursive();
-    ui_state.set(UiState::Notice)
-        .unwrap_or_else(|e| warn!("{e:?}"));
+    if failure.is_none() {
+        ui_state.set(UiState::Notice)
+            .unwrap_or_else(|e| warn!("{e:?}"));
+    }
 }
 
 pub fn load(
@@ -108,8 +115,12 @@ pub fn load(
     mut windows: ResMut<Windows>,
 )
 {
-    let aerugo = crate::utils::load_aerugo();
-    aerugo.validate().expect("Invalid aerugo scenario");
+    let aerugo = crate::utils::load_aerugo()
+        .and_then(|aerugo| { aerugo.validate().map(|_| aerugo) })
+        .unwrap_or_else(|error| {
+            commands.insert_resource(AerugoFailure { error, fatal: true });
+            Aerugo::default()
+        });
     let saves = pre_load_saves(&aerugo);
     commands.insert_resource(aerugo);
     commands.insert_resource(saves);
@@ -128,9 +139,19 @@ pub fn preload_assets(
     aerugo: Res<Aerugo>,
 )
 {
+    for character in aerugo.characters.values() {
+        for file in character.files() {
+            let _ = asset_server.load_untyped(file);
+        }
+    }
     for step in &aerugo.steps {
         match &step.inner {
-            Steps::ImageSelect { .. } => {}
+            Steps::ImageSelect { background, options } => {
+                let _ = asset_server.load_untyped(background);
+                for (sprite, _) in options.values() {
+                    let _ = asset_server.load_untyped(sprite);
+                }
+            }
             Steps::SpriteNarrator(cmd) => {
                 match cmd {
                     NarratorCommand::Set { sprite, .. } => {
@@ -175,6 +196,23 @@ pub fn preload_assets(
                     _ => {}
                 }
             }
+            Steps::Music(cmd) => {
+                match cmd {
+                    MusicCommand::Play { music, .. } | MusicCommand::Crossfade { music, .. } => {
+                        let _ = asset_server.load_untyped(music);
+                    }
+                    _ => {}
+                }
+            }
+            Steps::Particles(ParticlesCommand::Start { sprite: Some(sprite), .. }) => {
+                let _ = asset_server.load_untyped(sprite);
+            }
+            Steps::Sound { sound } => {
+                let _ = asset_server.load_untyped(sound);
+            }
+            Steps::Text { voice: Some(voice), .. } => {
+                let _ = asset_server.load_untyped(voice);
+            }
             _ => {}
         }
     }
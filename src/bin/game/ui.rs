@@ -11,7 +11,7 @@ pub use main_menu::*;
 pub use pause::*;
 pub use save_load::*;
 
-use crate::game::GameState;
+use crate::game::{AerugoFailure, GameState};
 use crate::settings::{Resolution, Settings};
 use crate::translator::{Lang, NewLang, Translator};
 
@@ -114,6 +114,10 @@ impl Plugin for UiPlugin {
                 SystemSet::on_exit(UiState::Pause)
                     .with_system(hide_game_menu)
             )
+            .add_system_set(
+                SystemSet::on_update(UiState::Error)
+                    .with_system(error_ui)
+            )
         ;
     }
 }
@@ -128,6 +132,7 @@ pub enum UiState {
     Load,
     Game,
     Pause,
+    Error,
 }
 
 
@@ -160,6 +165,7 @@ pub fn relative(
     for event in resize_event.iter() {
         game_ui.resize_relative(&mut sprite_query, &mut atlas_query, event.width, event.height);
         game_ui.text.resize_relative(&mut style_query, event.width, event.height);
+        game_ui.nvl.resize_relative(&mut style_query, event.width);
     }
 }
 
@@ -279,6 +285,10 @@ pub fn settings_ui(
                 );
                 ui.label(translator.get(&settings.lang, "Sec/Char"));
             });
+            ui.horizontal(|ui| {
+                ui.label(translator.get(&settings.lang, "ChoiceTimers"));
+                ui.checkbox(&mut settings.choice_timers, "");
+            });
 
 
             if current != *settings {
@@ -309,3 +319,40 @@ pub fn settings_ui(
             },
         );
 }
+
+pub fn error_ui(
+    mut commands: Commands,
+    mut e_ctx: ResMut<bevy_egui::EguiContext>,
+    failure: Option<Res<AerugoFailure>>,
+    settings: Res<Settings>,
+    mut translator: ResMut<Translator>,
+    mut ui_state: ResMut<State<UiState>>,
+    mut exit: EventWriter<AppExit>,
+)
+{
+    let failure = match failure {
+        None => { return; }
+        Some(failure) => { failure }
+    };
+    egui::CentralPanel::default().show(
+        e_ctx.ctx_mut(),
+        |ui| {
+            ui.heading(translator.get(&settings.lang, "ScenarioError"));
+            ui.separator();
+            ui.label(failure.error.to_string());
+            ui.separator();
+            ui.horizontal(|ui| {
+                if !failure.fatal {
+                    if ui.button(translator.get(&settings.lang, "MainMenu")).clicked() {
+                        commands.remove_resource::<AerugoFailure>();
+                        ui_state.set(UiState::MainMenu)
+                            .unwrap_or_else(|e| warn!("{e:?}"));
+                    }
+                }
+                if ui.button(translator.get(&settings.lang, "Exit")).clicked() {
+                    exit.send(AppExit);
+                }
+            });
+        },
+    );
+}
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use aerugo::ParticleKind;
+
 use crate::utils::*;
 
 use super::*;
@@ -184,6 +186,107 @@ impl PhraseUI {
     }
 }
 
+#[derive(Debug, Resource)]
+pub struct ImageSelectUI {
+    pub(crate) root: Entity,
+    pub(crate) is_visible: bool,
+}
+
+impl ImageSelectUI {
+    /// Option positions are pixels of this frame, scaled to window
+    pub const REFERENCE: Vec2 = Vec2::new(1920.0, 1080.0);
+    pub const DEFAULT: Color = Color::rgba(1.0, 1.0, 1.0, 0.85);
+    pub const HOVERED: Color = Color::rgba(1.0, 1.0, 1.0, 1.0);
+
+    fn _show(&self, query: &mut Query<&mut Style>) {
+        query.get_mut(self.root).unwrap().display = Display::Flex;
+    }
+
+    pub fn show(&self, query: &mut Query<&mut Style>) {
+        if self.is_visible { self._show(query) }
+    }
+
+    pub fn force_show(&mut self, query: &mut Query<&mut Style>) {
+        self.is_visible = true;
+        self._show(query);
+    }
+
+    pub fn force_hide(&mut self, query: &mut Query<&mut Style>) {
+        self.is_visible = false;
+        query.get_mut(self.root).unwrap().display = Display::None;
+    }
+}
+
+/// Text field of `Steps::Input`
+#[derive(Debug, Resource)]
+pub struct InputUI {
+    pub(crate) root: Entity,
+    pub(crate) is_visible: bool,
+    pub(crate) prompt: Entity,
+    pub(crate) field: Entity,
+}
+
+impl InputUI {
+    fn _show(&self, query: &mut Query<&mut Style>) {
+        query.get_mut(self.root).unwrap().display = Display::Flex;
+    }
+
+    pub fn show(&self, query: &mut Query<&mut Style>) {
+        if self.is_visible { self._show(query) }
+    }
+
+    pub fn force_show(&mut self, query: &mut Query<&mut Style>) {
+        self.is_visible = true;
+        self._show(query);
+    }
+
+    pub fn force_hide(&mut self, query: &mut Query<&mut Style>) {
+        self.is_visible = false;
+        query.get_mut(self.root).unwrap().display = Display::None;
+    }
+}
+
+/// Full-screen panel of `TextModeCommand::Nvl`, texts of the page pile up in one flow
+#[derive(Debug, Resource)]
+pub struct NvlUI {
+    pub(crate) root: Entity,
+    pub(crate) is_visible: bool,
+    pub(crate) text: Entity,
+}
+
+impl NvlUI {
+    fn _show(&self, query: &mut Query<&mut Style>) {
+        query.get_mut(self.root).unwrap().display = Display::Flex;
+    }
+
+    pub fn show(&self, query: &mut Query<&mut Style>) {
+        if self.is_visible { self._show(query) }
+    }
+
+    pub fn force_show(&mut self, query: &mut Query<&mut Style>) {
+        self.is_visible = true;
+        self._show(query);
+    }
+
+    pub fn force_hide(&mut self, query: &mut Query<&mut Style>) {
+        self.is_visible = false;
+        query.get_mut(self.root).unwrap().display = Display::None;
+    }
+
+    pub fn resize_relative(&self, style_query: &mut Query<&mut Style>, width: f32) {
+        style_query.get_mut(self.text).unwrap()
+            .max_size.width = Val::Px(width * 0.8);
+    }
+}
+
+/// Window sized overlays of [`ScreenCommand`](aerugo::ScreenCommand), under the text box
+#[derive(Debug, Resource)]
+pub struct ScreenUI {
+    pub(crate) fade: Entity,
+    pub(crate) flash: Entity,
+    pub(crate) vignette: Entity,
+}
+
 #[derive(Debug, Resource)]
 pub struct GameUI {
     pub(crate) ui_root: Entity,
@@ -192,15 +295,24 @@ pub struct GameUI {
     pub(crate) scene: Entity,
     pub(crate) scene_visible: bool,
     pub(crate) sprites: HashMap<String, Entity>,
+    /// Emitters, particles are their children
+    pub(crate) particles: HashMap<ParticleKind, Entity>,
 
     pub(crate) text: TextUI,
+    pub(crate) nvl: NvlUI,
     pub(crate) phrase: PhraseUI,
+    pub(crate) image_select: ImageSelectUI,
+    pub(crate) input: InputUI,
+    pub(crate) screen: ScreenUI,
 }
 
 impl GameUI {
     fn _show_game(&self, query: &mut Query<&mut Style>, query_2d: &mut Query<&mut Visibility>) {
         self.text.show(query);
+        self.nvl.show(query);
         self.phrase.show(query);
+        self.image_select.show(query);
+        self.input.show(query);
         query.get_mut(self.ui_root).unwrap().display = Display::Flex;
         if self.background_visible {
             query_2d.get_mut(self.background).unwrap().is_visible = true;
@@ -208,7 +320,7 @@ impl GameUI {
         if self.scene_visible {
             query_2d.get_mut(self.scene).unwrap().is_visible = true;
         }
-        self.sprites.values().for_each(|e| {
+        self.sprites.values().chain(self.particles.values()).for_each(|e| {
             query_2d.get_mut(*e).unwrap().is_visible = true;
         });
     }
@@ -216,7 +328,7 @@ impl GameUI {
         query.get_mut(self.ui_root).unwrap().display = Display::None;
         query_2d.get_mut(self.background).unwrap().is_visible = false;
         query_2d.get_mut(self.scene).unwrap().is_visible = false;
-        self.sprites.values().for_each(|e| {
+        self.sprites.values().chain(self.particles.values()).for_each(|e| {
             query_2d.get_mut(*e).unwrap().is_visible = false;
         });
     }
@@ -269,10 +381,6 @@ impl GameUI {
         if let Ok(mut atlas_sprite) = atlas_query.get_mut(self.scene) {
             atlas_sprite.custom_size = Some(Vec2::new(width, height));
         }
-        for sprite in self.sprites.values() {
-            sprite_query.get_mut(*sprite).unwrap()
-                .custom_size = Some(Vec2::new(width, height));
-        }
     }
 }
 
@@ -1,7 +1,5 @@
 #![allow(dead_code)]
 
-use std::io::Read;
-
 use bevy::asset::{Asset, LoadState};
 use bevy::ecs::{
     schedule::ShouldRun,
@@ -9,7 +7,7 @@ use bevy::ecs::{
 };
 use bevy::prelude::*;
 
-use aerugo::Aerugo;
+use aerugo::{Aerugo, AerugoError};
 
 use crate::startup::AssetCache;
 
@@ -19,10 +17,14 @@ pub const BTN_PRESSED: Color = Color::DARK_GRAY;
 
 pub const TRANSPARENT: Color = Color::rgba(1.0, 1.0, 1.0, 0.0);
 pub const GLASS_RED: Color = Color::rgba(1.0, 0.0, 0.0, 0.5);
+pub const GLASS_DARK: Color = Color::rgba(0.0, 0.0, 0.0, 0.7);
 
 pub const Z_SCENE: f32 = 15.0;
 pub const Z_BACKGROUND: f32 = 5.0;
 pub const Z_SPRITE: f32 = 10.0;
+pub const Z_PARTICLES: f32 = 7.5;
+/// Between sprite layers, keeps all of them under the scene
+pub const Z_SPRITE_LAYER: f32 = 0.01;
 pub const Y_SPRITE: f32 = 0.0;
 
 pub const SIZE_ALL: Size = Size {
@@ -80,15 +82,8 @@ pub const FLOW_MAX_DEFAULT: Size = Size {
     height: Val::Undefined,
 };
 
-pub fn load_aerugo() -> Aerugo {
-    const SCENARIO_PATH: &str = "scenario.ron";
-    let mut file = std::fs::File::options()
-        .read(true).write(true).create(true)
-        .open(SCENARIO_PATH)
-        .unwrap();
-    let mut aerugo = String::new();
-    file.read_to_string(&mut aerugo).unwrap();
-    ron::from_str(&aerugo).unwrap()
+pub fn load_aerugo() -> Result<Aerugo, AerugoError> {
+    Aerugo::load(std::env::current_dir().unwrap())
 }
 
 #[allow(dead_code)]
@@ -155,5 +150,8 @@ impl<'w, 's> CachedAssetServer<'w, 's> {
 pub const FONT_DIALOG: &str = "fonts/CormorantGaramond-SemiBold.ttf";
 pub const FONT_NAME: &str = "fonts/CormorantGaramond-SemiBold.ttf";
 pub const FONT_FLOW: &str = "fonts/CormorantGaramond-SemiBold.ttf";
+pub const FONT_FLOW_BOLD: &str = "fonts/CormorantGaramond-Bold.ttf";
+pub const FONT_FLOW_ITALIC: &str = "fonts/CormorantGaramond-SemiBoldItalic.ttf";
+pub const FONT_FLOW_BOLD_ITALIC: &str = "fonts/CormorantGaramond-BoldItalic.ttf";
 pub const FONT_MAIN_MENU: &str = "fonts/Bitter-Bold.ttf";
 pub const FONT_DEFAULT: &str = "fonts/Bitter-Medium.ttf";
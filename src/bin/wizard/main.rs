@@ -4,7 +4,6 @@ mod obfuscation;
 
 use std::error::Error;
 use std::fs;
-use std::io::Read;
 use std::path::{Path, PathBuf};
 use bevy::utils::default;
 use aerugo::*;
@@ -13,13 +12,13 @@ use sha3::{Digest, Sha3_256};
 use uuid::Uuid;
 use crate::obfuscation::obfuscation;
 
-const SCENARIO_PATH: &str = "scenario.ron";
-
 fn main() -> Result<(), Box<dyn Error>> {
     let command = std::env::args().nth(1).expect("Command expected");
     match command.as_str() {
         "obfuscation" => { obfuscation()? }
         "fix" => { fix()? }
+        "lint" => { lint()? }
+        "explore" => { explore()? }
         "help" => {
             todo!("all commands");
         }
@@ -28,23 +27,19 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-pub fn get_aerugo() -> Aerugo {
-    let mut file = std::fs::File::options()
-        .read(true).write(true).create(true)
-        .open(SCENARIO_PATH)
-        .unwrap();
-    let mut buff = String::new();
-    file.read_to_string(&mut buff).unwrap();
-    ron::from_str::<Aerugo>(&buff).unwrap_or_default()
+/// Loaded and validated scenario, commands refuse to run on a broken one
+pub fn get_aerugo() -> Result<Aerugo, AerugoError> {
+    let aerugo = Aerugo::load("")?;
+    aerugo.validate()?;
+    Ok(aerugo)
 }
 
 pub fn set_aerugo<F: AsRef<Path>>(aerugo: &Aerugo, path: F) {
-    let aerugo = ron::ser::to_string_pretty(&aerugo, default()).unwrap();
-    fs::write(Path::new(path.as_ref()).join(SCENARIO_PATH), aerugo.as_bytes()).unwrap();
+    aerugo.dump(path).unwrap();
 }
 
 pub fn fix() -> Result<(), Box<dyn Error>> {
-    let aerugo = get_aerugo();
+    let aerugo = get_aerugo()?;
     let mut internationale = Internationale::load();
     let mut last = Uuid::default();
     for step in &aerugo.steps {
@@ -64,7 +59,7 @@ pub fn fix() -> Result<(), Box<dyn Error>> {
             }
         }
         match step.inner {
-            Steps::Text { .. } | Steps::Phrase { .. } => {
+            Steps::Text { .. } | Steps::Phrase { .. } | Steps::Input { .. } => {
                 last = step.id;
             }
             _ => {}
@@ -81,4 +76,44 @@ pub fn fix() -> Result<(), Box<dyn Error>> {
         )?
     }
     Ok(())
-}
\ No newline at end of file
+}
+
+pub fn lint() -> Result<(), Box<dyn Error>> {
+    let aerugo = get_aerugo()?;
+    let diagnostics = aerugo.lint();
+    for diagnostic in &diagnostics {
+        println!("{}", diagnostic);
+    }
+    println!("Found {} problems", diagnostics.len());
+    Ok(())
+}
+
+pub fn explore() -> Result<(), Box<dyn Error>> {
+    const MAX_STATES: usize = 100_000;
+
+    let aerugo = get_aerugo()?;
+    let report = aerugo.explore(MAX_STATES);
+    let name = |id: &Uuid| {
+        aerugo.get(id).map(|s| { s.name.clone() }).unwrap_or_default()
+    };
+    if report.truncated {
+        println!("Stopped after {} states, results are incomplete", MAX_STATES);
+    }
+    for id in &report.endings {
+        println!("ending {} ({:?})", id, name(id));
+    }
+    for id in &report.unreached {
+        println!("never reached {} ({:?})", id, name(id));
+    }
+    for id in &report.loops {
+        println!("loops without await {} ({:?})", id, name(id));
+    }
+    for (id, error) in &report.errors {
+        println!("broken at {} ({:?}): {}", id, name(id), error);
+    }
+    match report.routes {
+        None => { println!("Routes: unbounded, choices can loop"); }
+        Some(routes) => { println!("Routes: {}", routes); }
+    }
+    Ok(())
+}
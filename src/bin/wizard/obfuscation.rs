@@ -5,7 +5,7 @@ use crate::*;
 
 pub fn obfuscation() -> Result<(), Box<dyn Error>> {
     let game = build_game()?;
-    let mut aerugo = get_aerugo();
+    let mut aerugo = get_aerugo()?;
     let dst = std::env::current_dir().unwrap()
         .join("target")
         .join("obfuscated");
@@ -36,10 +36,34 @@ pub fn obfuscation() -> Result<(), Box<dyn Error>> {
         fs::rename(new_assets.join(path), new_assets.join(&new_path)).unwrap();
     };
 
+    // names match sprite names, layer keys stay
+    aerugo.characters = std::mem::take(&mut aerugo.characters)
+        .into_iter()
+        .map(|(name, mut character)| {
+            _sha3f(&mut character.base);
+            character.outfits.values_mut().for_each(|file| { _sha3f(file) });
+            character.expressions.values_mut().for_each(|file| { _sha3f(file) });
+            (get_sha3(&name), character)
+        })
+        .collect();
     for step in aerugo.steps.iter_mut() {
         match &mut step.inner {
             Steps::Jump { .. } => {}
-            Steps::ImageSelect { .. } => {}
+            Steps::Character(cmd) => {
+                match cmd {
+                    CharacterCommand::Show { name, .. }
+                    | CharacterCommand::Outfit { name, .. }
+                    | CharacterCommand::Expression { name, .. } => {
+                        _sha3(name);
+                    }
+                    CharacterCommand::None => {}
+                }
+            }
+            Steps::ImageSelect { background, options } => {
+                _sha3f(background);
+                // keys stay, conditions check them
+                options.values_mut().for_each(|(sprite, _)| { _sha3f(sprite) });
+            }
             Steps::SpriteNarrator(cmd) => {
                 match cmd {
                     NarratorCommand::Set { name, sprite } => {
@@ -66,26 +90,29 @@ pub fn obfuscation() -> Result<(), Box<dyn Error>> {
                         _sha3f(sprite);
                         _sha3(name);
                     }
-                    SpriteCommand::FadeOut { name } => {
+                    SpriteCommand::FadeOut { name, .. } => {
                         _sha3(name);
                     }
                     SpriteCommand::LeftIn { sprite, name, .. } => {
                         _sha3f(sprite);
                         _sha3(name);
                     }
-                    SpriteCommand::LeftOut { name } => {
+                    SpriteCommand::LeftOut { name, .. } => {
                         _sha3(name);
                     }
                     SpriteCommand::RightIn { sprite, name, .. } => {
                         _sha3f(sprite);
                         _sha3(name);
                     }
-                    SpriteCommand::RightOut { name } => {
+                    SpriteCommand::RightOut { name, .. } => {
                         _sha3(name);
                     }
                     SpriteCommand::Move { name, .. } => {
                         _sha3(name);
                     }
+                    SpriteCommand::Transform { name, .. } => {
+                        _sha3(name);
+                    }
                 }
             }
             Steps::Background(cmd) => {
@@ -111,12 +138,42 @@ pub fn obfuscation() -> Result<(), Box<dyn Error>> {
                     SceneCommand::None => {}
                 }
             }
-            Steps::Text { author, texts } => {
+            Steps::Particles(ParticlesCommand::Start { sprite: Some(sprite), .. }) => {
+                _sha3f(sprite);
+            }
+            Steps::Music(cmd) => {
+                match cmd {
+                    MusicCommand::Play { music, .. } => {
+                        _sha3f(music);
+                    }
+                    MusicCommand::Crossfade { music, .. } => {
+                        _sha3f(music);
+                    }
+                    MusicCommand::Stop => {}
+                    MusicCommand::None => {}
+                }
+            }
+            Steps::Sound { sound } => {
+                _sha3f(sound);
+            }
+            Steps::Text { author, texts, voice } => {
                 *author = default();
                 *texts = default();
+                if let Some(voice) = voice {
+                    _sha3f(voice);
+                }
+            }
+            Steps::Phrase { phrases, .. } => {
+                for phrase in phrases.iter_mut() {
+                    phrase.verbose = default();
+                    if let Some(reason) = &mut phrase.disabled {
+                        *reason = default();
+                    }
+                }
             }
-            Steps::Phrase { phrases } => {
-                phrases.iter_mut().for_each(|phrase| { phrase.1 = default() });
+            Steps::Input { prompt, default: value, .. } => {
+                *prompt = default();
+                *value = default();
             }
             _ => {}
         }
@@ -0,0 +1,89 @@
+use std::path::Path;
+
+use super::*;
+
+pub const SCENARIO_PATH: &str = "scenario.ron";
+pub const CHAPTERS_PATH: &str = "chapters.ron";
+
+/// List of scenario files, assembled into one [`Aerugo`] in order
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChapterManifest {
+    pub chapters: Vec<String>,
+}
+
+/// Chapter file and ids of steps loaded from it
+#[derive(Debug, Clone, Default, Hash)]
+pub struct Chapter {
+    pub path: String,
+    pub steps: Vec<Uuid>,
+}
+
+impl Aerugo {
+    /// Load [`CHAPTERS_PATH`] manifest chapters, or single [`SCENARIO_PATH`] without manifest
+    pub fn load<P: AsRef<Path>>(base: P) -> Result<Aerugo, AerugoError> {
+        let base = base.as_ref();
+        let manifest = base.join(CHAPTERS_PATH);
+        if !manifest.exists() {
+            return Self::load_file(base.join(SCENARIO_PATH));
+        }
+
+        let manifest: ChapterManifest = ron::from_str(&std::fs::read_to_string(&manifest)?)
+            .map_err(|e| { AerugoError::Parse(format!("{}: {}", CHAPTERS_PATH, e)) })?;
+        let mut steps = vec![];
+        let mut characters = BTreeMap::new();
+        let mut chapters = vec![];
+        for path in manifest.chapters {
+            let chapter = Self::load_file(base.join(&path))?;
+            chapters.push(Chapter {
+                path,
+                steps: chapter.steps.iter().map(|s| { s.id }).collect(),
+            });
+            steps.extend(chapter.steps);
+            characters.extend(chapter.characters);
+        }
+        let mut aerugo = Aerugo::new(steps);
+        aerugo.characters = characters;
+        aerugo.chapters = chapters;
+        Ok(aerugo)
+    }
+
+    fn load_file<P: AsRef<Path>>(path: P) -> Result<Aerugo, AerugoError> {
+        let data = std::fs::read_to_string(&path)?;
+        Aerugo::parse(&data)
+            .map_err(|e| { AerugoError::Parse(format!("{}: {}", path.as_ref().display(), e)) })
+    }
+
+    /// Write steps back to chapter files, new steps go to the chapter of the previous step
+    pub fn dump<P: AsRef<Path>>(&self, base: P) -> Result<(), AerugoError> {
+        let base = base.as_ref();
+        if self.chapters.is_empty() {
+            let data = ron::ser::to_string_pretty(self, Default::default())?;
+            std::fs::write(base.join(SCENARIO_PATH), data.as_bytes())?;
+            return Ok(());
+        }
+
+        let owners: HashMap<Uuid, usize> = self.chapters.iter()
+            .enumerate()
+            .flat_map(|(i, c)| { c.steps.iter().map(move |id| { (*id, i) }) })
+            .collect();
+        let mut chapters: Vec<Aerugo> = self.chapters.iter()
+            .map(|_| { Aerugo::new(vec![]) })
+            .collect();
+        let mut current = 0;
+        for step in &self.steps {
+            if let Some(&owner) = owners.get(&step.id) {
+                current = owner;
+            }
+            chapters[current].steps.push(step.clone());
+        }
+        // characters are shared, keep them with the first chapter
+        if let Some(first) = chapters.first_mut() {
+            first.characters = self.characters.clone();
+        }
+        for (chapter, aerugo) in self.chapters.iter().zip(chapters) {
+            let data = ron::ser::to_string_pretty(&aerugo, Default::default())?;
+            std::fs::write(base.join(&chapter.path), data.as_bytes())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use super::*;
+
+/// Layered sprite of a character, every layer is window sized and drawn over the base
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Character {
+    /// Body image
+    pub base: String,
+    /// Used when shown without position
+    #[serde(default)]
+    pub position: f32,
+    /// name: image
+    #[serde(default)]
+    pub outfits: BTreeMap<String, String>,
+    /// name: image, drawn over outfit
+    #[serde(default)]
+    pub expressions: BTreeMap<String, String>,
+}
+
+impl std::hash::Hash for Character {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ron::to_string(self).unwrap().hash(state)
+    }
+}
+
+impl Character {
+    pub fn outfit(&self, outfit: &Option<String>) -> Option<&String> {
+        outfit.as_ref().and_then(|o| { self.outfits.get(o) })
+    }
+
+    pub fn expression(&self, expression: &Option<String>) -> Option<&String> {
+        expression.as_ref().and_then(|e| { self.expressions.get(e) })
+    }
+
+    /// Every image of character
+    pub fn files(&self) -> impl Iterator<Item=&String> {
+        std::iter::once(&self.base)
+            .chain(self.outfits.values())
+            .chain(self.expressions.values())
+    }
+}
+
+/// Commands for characters of [`Aerugo::characters`], shown character is a sprite
+/// with the same name, so [`SpriteCommand`] moves, transforms and removes it
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub enum CharacterCommand {
+    Show {
+        name: String,
+        #[serde(default)]
+        outfit: Option<String>,
+        #[serde(default)]
+        expression: Option<String>,
+        /// Position of character definition when missing
+        #[serde(default)]
+        position: Option<f32>,
+        #[serde(default)]
+        transform: SpriteTransform,
+        /// Seconds of fade in, at once when missing
+        #[serde(default)]
+        duration: Option<f32>,
+    },
+    /// Swap outfit layer only, `None` removes it
+    Outfit {
+        name: String,
+        outfit: Option<String>,
+    },
+    /// Swap expression layer only, `None` removes it
+    Expression {
+        name: String,
+        expression: Option<String>,
+    },
+    #[default]
+    None,
+}
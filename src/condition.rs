@@ -1,6 +1,10 @@
+use std::cmp::Ordering;
+
 use uuid::Uuid;
 use serde::{Serialize, Deserialize};
 
+use crate::{AerugoState, Variable};
+
 #[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub enum Condition {
     True,
@@ -14,6 +18,21 @@ pub enum Condition {
     Or(Box<Condition>, Box<Condition>),
     GTE(Vec<Condition>, usize),
     LTE(Vec<Condition>, usize),
+    /// Story variable equals value
+    Eq {
+        var: String,
+        value: Variable,
+    },
+    /// Story variable greater than value
+    Gt {
+        var: String,
+        value: Variable,
+    },
+    /// Story variable less than value
+    Lt {
+        var: String,
+        value: Variable,
+    },
 }
 
 impl Default for Condition {
@@ -23,29 +42,82 @@ impl Default for Condition {
 }
 
 impl Condition {
-    pub(crate) fn resolve(&self, select_story: &Vec<(Uuid, String)>) -> bool {
+    pub fn resolve(&self, state: &AerugoState) -> bool {
         match self {
             Condition::True => { true }
             Condition::False => { false }
             Condition::Check { step, val } => {
-                select_story.iter().find(|(s, v)| {
-                    s == step && v == val
-                }).is_some()
+                state.select_story.get(step) == Some(val)
             }
             Condition::Not(c) => {
-                !c.resolve(select_story)
+                !c.resolve(state)
             }
             Condition::And(l, r) => {
-                l.resolve(select_story) && r.resolve(select_story)
+                l.resolve(state) && r.resolve(state)
             }
             Condition::Or(l, r) => {
-                l.resolve(select_story) || r.resolve(select_story)
+                l.resolve(state) || r.resolve(state)
             }
             Condition::GTE(conditions, count) => {
-                conditions.iter().filter(|c| { c.resolve(select_story) }).count() >= *count
+                conditions.iter().filter(|c| { c.resolve(state) }).count() >= *count
             }
             Condition::LTE(conditions, count) => {
-                conditions.iter().filter(|c| { c.resolve(select_story) }).count() <= *count
+                conditions.iter().filter(|c| { c.resolve(state) }).count() <= *count
+            }
+            Condition::Eq { var, value } => {
+                Self::compare(state, var, value) == Some(Ordering::Equal)
+            }
+            Condition::Gt { var, value } => {
+                Self::compare(state, var, value) == Some(Ordering::Greater)
+            }
+            Condition::Lt { var, value } => {
+                Self::compare(state, var, value) == Some(Ordering::Less)
+            }
+        }
+    }
+
+    fn compare(state: &AerugoState, var: &str, value: &Variable) -> Option<Ordering> {
+        state.variables.get(var)
+            .and_then(|v| { v.partial_cmp(value) })
+    }
+
+    /// All `Condition::Check` as (step, val)
+    pub fn checks<'a>(&'a self, acc: &mut Vec<(&'a Uuid, &'a String)>) {
+        match self {
+            Condition::True | Condition::False => {}
+            Condition::Check { step, val } => {
+                acc.push((step, val));
+            }
+            Condition::Not(c) => {
+                c.checks(acc);
+            }
+            Condition::And(l, r) | Condition::Or(l, r) => {
+                l.checks(acc);
+                r.checks(acc);
+            }
+            Condition::GTE(conditions, _) | Condition::LTE(conditions, _) => {
+                conditions.iter().for_each(|c| { c.checks(acc) });
+            }
+            Condition::Eq { .. } | Condition::Gt { .. } | Condition::Lt { .. } => {}
+        }
+    }
+
+    /// All story variables used in condition
+    pub fn variables<'a>(&'a self, acc: &mut Vec<&'a String>) {
+        match self {
+            Condition::True | Condition::False | Condition::Check { .. } => {}
+            Condition::Not(c) => {
+                c.variables(acc);
+            }
+            Condition::And(l, r) | Condition::Or(l, r) => {
+                l.variables(acc);
+                r.variables(acc);
+            }
+            Condition::GTE(conditions, _) | Condition::LTE(conditions, _) => {
+                conditions.iter().for_each(|c| { c.variables(acc) });
+            }
+            Condition::Eq { var, .. } | Condition::Gt { var, .. } | Condition::Lt { var, .. } => {
+                acc.push(var);
             }
         }
     }
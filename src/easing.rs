@@ -0,0 +1,65 @@
+use std::f32::consts::{PI, TAU};
+
+use serde::{Deserialize, Serialize};
+
+/// Progress curve of animations, maps linear time `0..=1` to progress
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    /// Bounces at the end
+    Bounce,
+    /// Overshoots and springs back at the end
+    Elastic,
+}
+
+impl Easing {
+    pub fn apply(&self, t: f32) -> f32 {
+        // timers of zero duration give NaN, they are finished
+        let t = if t.is_nan() { 1.0 } else { t.clamp(0.0, 1.0) };
+        match self {
+            Easing::Linear => { t }
+            Easing::EaseIn => { 1.0 - (t * PI / 2.0).cos() }
+            Easing::EaseOut => { (t * PI / 2.0).sin() }
+            Easing::EaseInOut => { (1.0 - (t * PI).cos()) / 2.0 }
+            Easing::CubicIn => { t * t * t }
+            Easing::CubicOut => { 1.0 - (1.0 - t).powi(3) }
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::Bounce => {
+                const N: f32 = 7.5625;
+                const D: f32 = 2.75;
+                if t < 1.0 / D {
+                    N * t * t
+                } else if t < 2.0 / D {
+                    let t = t - 1.5 / D;
+                    N * t * t + 0.75
+                } else if t < 2.5 / D {
+                    let t = t - 2.25 / D;
+                    N * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / D;
+                    N * t * t + 0.984375
+                }
+            }
+            Easing::Elastic => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    2f32.powf(-10.0 * t) * ((t * 10.0 - 0.75) * TAU / 3.0).sin() + 1.0
+                }
+            }
+        }
+    }
+}
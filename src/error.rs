@@ -0,0 +1,94 @@
+use std::fmt::{Display, Formatter};
+
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum AerugoError {
+    /// No step with this id in scenario
+    UnknownStep(Uuid),
+    /// Scenario flow ran past the last step
+    EndOfScenario,
+    DuplicateIds(Vec<Uuid>),
+    /// `Steps::Jump` of `step` points to missing `target`
+    InvalidJumpTarget {
+        step: Uuid,
+        target: Uuid,
+    },
+    Parse(String),
+    Io(String),
+    /// Story variable operation does not match variable type
+    VariableType(String),
+    /// `Steps::Return` with empty call stack
+    ReturnWithoutCall(Uuid),
+    /// Flow from step never reaches an await step
+    InfiniteLoop(Uuid),
+    /// Await step needs a selected option to continue
+    ChoiceRequired(Uuid),
+    /// Option is missing in choice step
+    InvalidChoice {
+        step: Uuid,
+        value: String,
+    },
+}
+
+impl Display for AerugoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AerugoError::UnknownStep(id) => {
+                write!(f, "Unknown step: {}", id)
+            }
+            AerugoError::EndOfScenario => {
+                write!(f, "Unexpected end of scenario")
+            }
+            AerugoError::DuplicateIds(ids) => {
+                let ids = ids.iter()
+                    .map(|id| id.to_string()).collect::<Vec<_>>();
+                write!(f, "Found duplicates: {}", ids.join(", "))
+            }
+            AerugoError::InvalidJumpTarget { step, target } => {
+                write!(f, "Step {} jumps to unknown step {}", step, target)
+            }
+            AerugoError::Parse(e) => {
+                write!(f, "Parse error: {}", e)
+            }
+            AerugoError::Io(e) => {
+                write!(f, "IO error: {}", e)
+            }
+            AerugoError::VariableType(var) => {
+                write!(f, "Invalid operation for variable type: {}", var)
+            }
+            AerugoError::ReturnWithoutCall(step) => {
+                write!(f, "Step {} returns without call", step)
+            }
+            AerugoError::InfiniteLoop(step) => {
+                write!(f, "Step {} loops without await step", step)
+            }
+            AerugoError::ChoiceRequired(step) => {
+                write!(f, "Step {} requires a choice", step)
+            }
+            AerugoError::InvalidChoice { step, value } => {
+                write!(f, "Step {} has no option {:?}", step, value)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AerugoError {}
+
+impl From<ron::error::SpannedError> for AerugoError {
+    fn from(e: ron::error::SpannedError) -> Self {
+        AerugoError::Parse(e.to_string())
+    }
+}
+
+impl From<ron::Error> for AerugoError {
+    fn from(e: ron::Error) -> Self {
+        AerugoError::Parse(e.to_string())
+    }
+}
+
+impl From<std::io::Error> for AerugoError {
+    fn from(e: std::io::Error) -> Self {
+        AerugoError::Io(e.to_string())
+    }
+}
@@ -0,0 +1,310 @@
+use std::collections::VecDeque;
+
+use super::*;
+
+/// Everything that affects flow, graphics are left out
+type RouteKey = (Uuid, BTreeMap<Uuid, String>, BTreeMap<String, Variable>, Vec<Uuid>);
+
+#[derive(Debug, Clone, Default)]
+pub struct Exploration {
+    /// Last await step of finished routes, in order of discovery
+    pub endings: Vec<Uuid>,
+    /// Distinct routes to any ending, `None` when choices can loop forever
+    pub routes: Option<u64>,
+    /// Steps not passed under any choice combination
+    pub unreached: Vec<Uuid>,
+    /// Steps where flow loops without ever reaching an await step
+    pub loops: Vec<Uuid>,
+    /// Routes broken by other errors, with the step they were at,
+    /// running past the last step without an await step too
+    pub errors: Vec<(Uuid, AerugoError)>,
+    /// Stopped at `max_states` or at the walk budget between await steps,
+    /// other fields are incomplete
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Edge {
+    State(usize),
+    Ending,
+    Broken,
+}
+
+impl Aerugo {
+    /// Breadth-first walk over every phrase and image choice,
+    /// states with same position, selections and variables are visited once
+    pub fn explore(&self, max_states: usize) -> Exploration {
+        let mut report = Exploration::default();
+        let mut reached = vec![false; self.steps.len()];
+        let mut keys: HashMap<RouteKey, usize> = HashMap::new();
+        let mut edges: Vec<Vec<Edge>> = Vec::new();
+        let mut queue: VecDeque<(usize, AerugoState)> = VecDeque::new();
+
+        let root = AerugoState::new(self)
+            .ok()
+            .and_then(|state| {
+                self.explore_collect(state, &mut reached, &mut report).ok()
+            });
+        if let Some(state) = root {
+            keys.insert(Self::route_key(&state), 0);
+            edges.push(Vec::new());
+            queue.push_back((0, state));
+        }
+
+        while let Some((node, state)) = queue.pop_front() {
+            let step = match state.step(self) {
+                Ok(step) => { step }
+                Err(error) => {
+                    report.errors.push((state.current, error));
+                    continue;
+                }
+            };
+            let choices: Vec<Option<String>> = match &step.inner {
+                Steps::Phrase { phrases, .. } => {
+                    phrases.iter()
+                        .filter(|option| { option.is_available(&state) })
+                        .map(|option| { Some(option.key.clone()) })
+                        .collect()
+                }
+                Steps::ImageSelect { options, .. } => {
+                    let mut keys: Vec<&String> = options.keys().collect();
+                    keys.sort();
+                    keys.into_iter().map(|key| { Some(key.clone()) }).collect()
+                }
+                _ => { vec![None] }
+            };
+            for choice in choices {
+                let mut next = state.clone();
+                if let Some(choice) = choice {
+                    next.select_unique(step.id, choice);
+                }
+                let collected = match next.next(self) {
+                    Ok(_) => { self.explore_collect(next, &mut reached, &mut report) }
+                    Err(AerugoError::EndOfScenario) => { Err(Edge::Ending) }
+                    Err(error) => {
+                        report.errors.push((step.id, error));
+                        Err(Edge::Broken)
+                    }
+                };
+                let edge = match collected {
+                    Err(Edge::Ending) => {
+                        if !report.endings.contains(&step.id) {
+                            report.endings.push(step.id);
+                        }
+                        Edge::Ending
+                    }
+                    Err(edge) => { edge }
+                    Ok(next) => {
+                        let key = Self::route_key(&next);
+                        match keys.get(&key) {
+                            Some(&known) => { Edge::State(known) }
+                            None if keys.len() >= max_states => {
+                                report.truncated = true;
+                                Edge::Broken
+                            }
+                            None => {
+                                let id = edges.len();
+                                keys.insert(key, id);
+                                edges.push(Vec::new());
+                                queue.push_back((id, next));
+                                Edge::State(id)
+                            }
+                        }
+                    }
+                };
+                edges[node].push(edge);
+            }
+        }
+
+        report.unreached = self.steps.iter()
+            .zip(reached)
+            .filter(|(_, reached)| { !reached })
+            .map(|(s, _)| { s.id })
+            .collect();
+        report.routes = Self::count_routes(&edges);
+        report
+    }
+
+    /// Collect up to the next await step, or how the route ends before it
+    fn explore_collect(
+        &self,
+        mut state: AerugoState,
+        reached: &mut [bool],
+        report: &mut Exploration,
+    ) -> Result<AerugoState, Edge> {
+        // passes over every step, counting loops may need a few
+        const WALK_PER_STEP: usize = 16;
+
+        let mut seen = HashSet::new();
+        // variables changing on every pass never repeat a key
+        let mut budget = self.steps.len().saturating_mul(WALK_PER_STEP);
+        let mut exhausted = false;
+        let result = state.collect_with(self, |state| {
+            if let Some(i) = self.position(&state.current) {
+                reached[i] = true;
+            }
+            if budget == 0 {
+                exhausted = true;
+                return Err(AerugoError::InfiniteLoop(state.current));
+            }
+            budget -= 1;
+            let key = (state.current, state.variables.clone(), state.call_stack.clone());
+            // unbounded recursion of calls counts as a loop too
+            if !seen.insert(key) || state.call_stack.len() > self.steps.len() {
+                return Err(AerugoError::InfiniteLoop(state.current));
+            }
+            Ok(())
+        });
+        match result {
+            Ok(_) => { Ok(state) }
+            // like the game and `DiagnosticKind::DeadEnd`, only an await step may end the story
+            Err(AerugoError::InfiniteLoop(_)) if exhausted => {
+                report.truncated = true;
+                Err(Edge::Broken)
+            }
+            Err(AerugoError::InfiniteLoop(step)) => {
+                if !report.loops.contains(&step) {
+                    report.loops.push(step);
+                }
+                Err(Edge::Broken)
+            }
+            Err(error) => {
+                report.errors.push((state.current, error));
+                Err(Edge::Broken)
+            }
+        }
+    }
+
+    fn route_key(state: &AerugoState) -> RouteKey {
+        (
+            state.current,
+            state.select_story.clone(),
+            state.variables.clone(),
+            state.call_stack.clone(),
+        )
+    }
+
+    /// Paths from the first state to endings, `None` on cycles
+    fn count_routes(edges: &[Vec<Edge>]) -> Option<u64> {
+        let mut incoming = vec![0usize; edges.len()];
+        for edge in edges.iter().flatten() {
+            if let Edge::State(n) = edge {
+                incoming[*n] += 1;
+            }
+        }
+        let mut order = Vec::with_capacity(edges.len());
+        let mut queue: VecDeque<usize> = (0..edges.len())
+            .filter(|&n| { incoming[n] == 0 })
+            .collect();
+        while let Some(n) = queue.pop_front() {
+            order.push(n);
+            for edge in &edges[n] {
+                if let Edge::State(m) = edge {
+                    incoming[*m] -= 1;
+                    if incoming[*m] == 0 {
+                        queue.push_back(*m);
+                    }
+                }
+            }
+        }
+        if order.len() != edges.len() {
+            return None;
+        }
+
+        let mut routes = vec![0u64; edges.len()];
+        for &n in order.iter().rev() {
+            routes[n] = edges[n].iter()
+                .map(|edge| {
+                    match edge {
+                        Edge::State(m) => { routes[*m] }
+                        Edge::Ending => { 1 }
+                        Edge::Broken => { 0 }
+                    }
+                })
+                .fold(0u64, |acc, r| { acc.saturating_add(r) });
+        }
+        // no first state, no routes
+        Some(routes.first().copied().unwrap_or(0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_step as step, test_text as text};
+
+    #[test]
+    fn routes_endings_and_unreached() {
+        let phrase = step(Steps::Phrase {
+            phrases: vec![PhraseOption::new("a", "A"), PhraseOption::new("b", "B")],
+            timeout: None,
+        });
+        let (lost, b, end) = (step(text("lost")), step(text("B")), step(text("End")));
+        let aerugo = Aerugo::new(vec![
+            phrase.clone(),
+            step(Steps::Jump {
+                condition: Some(Condition::Check { step: phrase.id, val: "b".to_string() }),
+                target: b.id,
+            }),
+            step(text("A")),
+            step(Steps::Jump { condition: None, target: end.id }),
+            lost.clone(),
+            b,
+            end.clone(),
+        ]);
+
+        let report = aerugo.explore(1000);
+        assert_eq!(report.endings, vec![end.id]);
+        assert_eq!(report.routes, Some(2));
+        assert_eq!(report.unreached, vec![lost.id]);
+        assert!(report.loops.is_empty() && report.errors.is_empty() && !report.truncated);
+    }
+
+    #[test]
+    fn choice_loop_has_no_route_count() {
+        let phrase = step(Steps::Phrase {
+            phrases: vec![PhraseOption::new("again", "Again"), PhraseOption::new("stop", "Stop")],
+            timeout: None,
+        });
+        let aerugo = Aerugo::new(vec![
+            phrase.clone(),
+            step(Steps::Jump {
+                condition: Some(Condition::Check { step: phrase.id, val: "again".to_string() }),
+                target: phrase.id,
+            }),
+            step(text("End")),
+        ]);
+
+        let report = aerugo.explore(1000);
+        assert_eq!(report.endings.len(), 1);
+        assert_eq!(report.routes, None);
+    }
+
+    #[test]
+    fn graphic_tail_is_not_an_ending() {
+        let sound = step(Steps::Sound { sound: "door".to_string() });
+        let aerugo = Aerugo::new(vec![step(text("Last")), sound.clone()]);
+
+        let report = aerugo.explore(1000);
+        assert!(report.endings.is_empty());
+        assert_eq!(report.errors, vec![(sound.id, AerugoError::EndOfScenario)]);
+        assert_eq!(report.routes, Some(0));
+    }
+
+    #[test]
+    fn counting_loop_is_truncated() {
+        let counter = step(Steps::ModifyVar {
+            var: "n".to_string(),
+            operation: VarOperation::Add(1),
+        });
+        let aerugo = Aerugo::new(vec![
+            step(Steps::SetVar { var: "n".to_string(), value: Variable::Int(0) }),
+            counter.clone(),
+            step(Steps::Jump { condition: None, target: counter.id }),
+        ]);
+
+        let report = aerugo.explore(1000);
+        assert!(report.truncated);
+        assert!(report.endings.is_empty());
+    }
+}
@@ -0,0 +1,493 @@
+use std::fmt::{Display, Formatter};
+
+use super::*;
+
+// region grammar
+// or      := and ("||" and)*
+// and     := unary ("&&" unary)*
+// unary   := "!" unary | primary
+// primary := "true" | "false" | "(" or ")"
+//          | "choice" "(" string ")" "==" string
+//          | "var" "(" string ")" ("==" | ">" | "<") value
+//          | ("at_least" | "at_most") "(" int ("," or)* ")"
+// value   := int | "true" | "false" | string
+// endregion
+
+/// Condition expression error, `position` is in chars
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ConditionError {
+    pub position: usize,
+    pub message: String,
+}
+
+impl Display for ConditionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ConditionError {}
+
+/// Step names for `choice(...)`, ambiguous and empty names fall back to ids
+#[derive(Debug, Clone, Default)]
+pub struct StepNames {
+    by_name: HashMap<String, Vec<Uuid>>,
+    by_id: HashMap<Uuid, String>,
+}
+
+impl StepNames {
+    pub fn new<'a, I: IntoIterator<Item = (&'a Uuid, &'a String)>>(steps: I) -> Self {
+        let mut names = StepNames::default();
+        for (id, name) in steps {
+            names.by_id.insert(*id, name.clone());
+            if !name.is_empty() {
+                names.by_name.entry(name.clone()).or_default().push(*id);
+            }
+        }
+        names
+    }
+
+    fn resolve(&self, name: &str) -> Result<Uuid, String> {
+        match self.by_name.get(name).map(|ids| { ids.as_slice() }) {
+            Some([id]) => { Ok(*id) }
+            Some(_) => { Err(format!("Ambiguous step name {:?}", name)) }
+            None => {
+                Uuid::parse_str(name).map_err(|_| { format!("Unknown step {:?}", name) })
+            }
+        }
+    }
+
+    fn verbose(&self, id: &Uuid) -> String {
+        match self.by_id.get(id) {
+            Some(name) if self.by_name.get(name).map(|ids| { ids.len() }) == Some(1) => {
+                name.clone()
+            }
+            _ => { id.to_string() }
+        }
+    }
+}
+
+impl From<&Aerugo> for StepNames {
+    fn from(aerugo: &Aerugo) -> Self {
+        StepNames::new(aerugo.steps.iter().map(|s| { (&s.id, &s.name) }))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Int(i64),
+    LParen,
+    RParen,
+    Comma,
+    And,
+    Or,
+    Not,
+    Eq,
+    Gt,
+    Lt,
+    End,
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Ident(ident) => { write!(f, "{}", ident) }
+            Token::Str(s) => { write!(f, "{:?}", s) }
+            Token::Int(n) => { write!(f, "{}", n) }
+            Token::LParen => { write!(f, "'('") }
+            Token::RParen => { write!(f, "')'") }
+            Token::Comma => { write!(f, "','") }
+            Token::And => { write!(f, "'&&'") }
+            Token::Or => { write!(f, "'||'") }
+            Token::Not => { write!(f, "'!'") }
+            Token::Eq => { write!(f, "'=='") }
+            Token::Gt => { write!(f, "'>'") }
+            Token::Lt => { write!(f, "'<'") }
+            Token::End => { write!(f, "end") }
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, ConditionError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    let error = |position: usize, message: &str| {
+        ConditionError { position, message: message.to_string() }
+    };
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i];
+        let pair = chars.get(i + 1).copied();
+        let token = match c {
+            _ if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '(' => { Token::LParen }
+            ')' => { Token::RParen }
+            ',' => { Token::Comma }
+            '>' => { Token::Gt }
+            '<' => { Token::Lt }
+            '!' => { Token::Not }
+            '&' if pair == Some('&') => {
+                i += 1;
+                Token::And
+            }
+            '|' if pair == Some('|') => {
+                i += 1;
+                Token::Or
+            }
+            '=' if pair == Some('=') => {
+                i += 1;
+                Token::Eq
+            }
+            '"' => {
+                let mut value = String::new();
+                loop {
+                    i += 1;
+                    match chars.get(i) {
+                        None => { return Err(error(start, "Unterminated string")); }
+                        Some('"') => { break; }
+                        Some('\\') => {
+                            i += 1;
+                            match chars.get(i) {
+                                Some('"') => { value.push('"') }
+                                Some('\\') => { value.push('\\') }
+                                Some('n') => { value.push('\n') }
+                                _ => { return Err(error(i, "Unknown escape")); }
+                            }
+                        }
+                        Some(c) => { value.push(*c) }
+                    }
+                }
+                Token::Str(value)
+            }
+            _ if c.is_ascii_digit() || (c == '-' && pair.is_some_and(|p| { p.is_ascii_digit() })) => {
+                i += 1;
+                while chars.get(i).is_some_and(|c| { c.is_ascii_digit() }) {
+                    i += 1;
+                }
+                let number: String = chars[start..i].iter().collect();
+                i -= 1;
+                Token::Int(number.parse().map_err(|_| { error(start, "Number out of range") })?)
+            }
+            _ if c.is_alphabetic() || c == '_' => {
+                while chars.get(i + 1).is_some_and(|c| { c.is_alphanumeric() || *c == '_' }) {
+                    i += 1;
+                }
+                Token::Ident(chars[start..=i].iter().collect())
+            }
+            _ => { return Err(error(start, &format!("Unexpected {:?}", c))); }
+        };
+        tokens.push((start, token));
+        i += 1;
+    }
+    tokens.push((chars.len(), Token::End));
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    names: &'a StepNames,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.pos].1
+    }
+
+    fn next(&mut self) -> (usize, Token) {
+        let token = self.tokens[self.pos].clone();
+        if token.1 != Token::End {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn error<T>(&self, message: String) -> Result<T, ConditionError> {
+        Err(ConditionError { position: self.tokens[self.pos].0, message })
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ConditionError> {
+        if *self.peek() == expected {
+            self.next();
+            Ok(())
+        } else {
+            self.error(format!("Expected {}, found {}", expected, self.peek()))
+        }
+    }
+
+    fn string(&mut self) -> Result<(usize, String), ConditionError> {
+        match self.tokens[self.pos].clone() {
+            (position, Token::Str(value)) => {
+                self.next();
+                Ok((position, value))
+            }
+            (_, token) => { self.error(format!("Expected string, found {}", token)) }
+        }
+    }
+
+    fn or(&mut self) -> Result<Condition, ConditionError> {
+        let mut condition = self.and()?;
+        while *self.peek() == Token::Or {
+            self.next();
+            condition = Condition::Or(Box::new(condition), Box::new(self.and()?));
+        }
+        Ok(condition)
+    }
+
+    fn and(&mut self) -> Result<Condition, ConditionError> {
+        let mut condition = self.unary()?;
+        while *self.peek() == Token::And {
+            self.next();
+            condition = Condition::And(Box::new(condition), Box::new(self.unary()?));
+        }
+        Ok(condition)
+    }
+
+    fn unary(&mut self) -> Result<Condition, ConditionError> {
+        if *self.peek() == Token::Not {
+            self.next();
+            return Ok(Condition::Not(Box::new(self.unary()?)));
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Condition, ConditionError> {
+        let (position, token) = self.next();
+        match token {
+            Token::LParen => {
+                let condition = self.or()?;
+                self.expect(Token::RParen)?;
+                Ok(condition)
+            }
+            Token::Ident(ident) => {
+                match ident.as_str() {
+                    "true" => { Ok(Condition::True) }
+                    "false" => { Ok(Condition::False) }
+                    "choice" => {
+                        self.expect(Token::LParen)?;
+                        let (name_position, name) = self.string()?;
+                        self.expect(Token::RParen)?;
+                        self.expect(Token::Eq)?;
+                        let (_, val) = self.string()?;
+                        let step = self.names.resolve(&name)
+                            .map_err(|message| { ConditionError { position: name_position, message } })?;
+                        Ok(Condition::Check { step, val })
+                    }
+                    "var" => {
+                        self.expect(Token::LParen)?;
+                        let (_, var) = self.string()?;
+                        self.expect(Token::RParen)?;
+                        let (operator_position, operator) = self.next();
+                        if !matches!(operator, Token::Eq | Token::Gt | Token::Lt) {
+                            return Err(ConditionError {
+                                position: operator_position,
+                                message: format!("Expected comparison, found {}", operator),
+                            });
+                        }
+                        let value = self.value()?;
+                        match operator {
+                            Token::Gt => { Ok(Condition::Gt { var, value }) }
+                            Token::Lt => { Ok(Condition::Lt { var, value }) }
+                            _ => { Ok(Condition::Eq { var, value }) }
+                        }
+                    }
+                    "at_least" | "at_most" => {
+                        self.expect(Token::LParen)?;
+                        let count = match self.peek() {
+                            Token::Int(count) if *count >= 0 => { *count as usize }
+                            token => { return self.error(format!("Expected count, found {}", token)); }
+                        };
+                        self.next();
+                        let mut conditions = Vec::new();
+                        while *self.peek() == Token::Comma {
+                            self.next();
+                            conditions.push(self.or()?);
+                        }
+                        self.expect(Token::RParen)?;
+                        if ident == "at_least" {
+                            Ok(Condition::GTE(conditions, count))
+                        } else {
+                            Ok(Condition::LTE(conditions, count))
+                        }
+                    }
+                    _ => {
+                        Err(ConditionError { position, message: format!("Unknown {:?}", ident) })
+                    }
+                }
+            }
+            token => {
+                Err(ConditionError { position, message: format!("Unexpected {}", token) })
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Variable, ConditionError> {
+        let value = match self.peek() {
+            Token::Int(n) => { Variable::Int(*n) }
+            Token::Str(s) => { Variable::Str(s.clone()) }
+            Token::Ident(ident) if ident == "true" => { Variable::Bool(true) }
+            Token::Ident(ident) if ident == "false" => { Variable::Bool(false) }
+            token => { return self.error(format!("Expected value, found {}", token)); }
+        };
+        self.next();
+        Ok(value)
+    }
+}
+
+fn quote(s: &str) -> String {
+    let escaped = s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{}\"", escaped)
+}
+
+impl Variable {
+    fn pretty(&self) -> String {
+        match self {
+            Variable::Int(n) => { n.to_string() }
+            Variable::Bool(b) => { b.to_string() }
+            Variable::Str(s) => { quote(s) }
+        }
+    }
+}
+
+impl Condition {
+    pub fn parse(text: &str, names: &StepNames) -> Result<Condition, ConditionError> {
+        let mut parser = Parser { tokens: tokenize(text)?, pos: 0, names };
+        let condition = parser.or()?;
+        if *parser.peek() != Token::End {
+            return parser.error(format!("Unexpected {}", parser.peek()));
+        }
+        Ok(condition)
+    }
+
+    /// Expression text, parses back to the same condition
+    pub fn pretty(&self, names: &StepNames) -> String {
+        let group = |c: &Condition, wrap: bool| {
+            if wrap {
+                format!("({})", c.pretty(names))
+            } else {
+                c.pretty(names)
+            }
+        };
+        match self {
+            Condition::True => { "true".to_string() }
+            Condition::False => { "false".to_string() }
+            Condition::Check { step, val } => {
+                format!("choice({}) == {}", quote(&names.verbose(step)), quote(val))
+            }
+            Condition::Not(c) => {
+                let wrap = matches!(**c, Condition::And(..) | Condition::Or(..));
+                format!("!{}", group(c, wrap))
+            }
+            Condition::And(l, r) => {
+                format!(
+                    "{} && {}",
+                    group(l, matches!(**l, Condition::Or(..))),
+                    group(r, matches!(**r, Condition::And(..) | Condition::Or(..))),
+                )
+            }
+            Condition::Or(l, r) => {
+                format!(
+                    "{} || {}",
+                    group(l, false),
+                    group(r, matches!(**r, Condition::Or(..))),
+                )
+            }
+            Condition::GTE(conditions, count) | Condition::LTE(conditions, count) => {
+                let name = match self {
+                    Condition::GTE(..) => { "at_least" }
+                    _ => { "at_most" }
+                };
+                let mut args = vec![count.to_string()];
+                args.extend(conditions.iter().map(|c| { c.pretty(names) }));
+                format!("{}({})", name, args.join(", "))
+            }
+            Condition::Eq { var, value } => {
+                format!("var({}) == {}", quote(var), value.pretty())
+            }
+            Condition::Gt { var, value } => {
+                format!("var({}) > {}", quote(var), value.pretty())
+            }
+            Condition::Lt { var, value } => {
+                format!("var({}) < {}", quote(var), value.pretty())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names() -> (StepNames, Uuid, Uuid) {
+        let (intro, twin) = (Uuid::new_v4(), Uuid::new_v4());
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let steps = [
+            (intro, "intro".to_string()),
+            (a, "twin".to_string()),
+            (b, "twin".to_string()),
+            (twin, "".to_string()),
+        ];
+        (StepNames::new(steps.iter().map(|(id, name)| { (id, name) })), intro, a)
+    }
+
+    fn position(text: &str) -> usize {
+        Condition::parse(text, &names().0).unwrap_err().position
+    }
+
+    #[test]
+    fn pretty_parses_back() {
+        let (names, intro, twin) = names();
+        let check = |step: Uuid, val: &str| { Condition::Check { step, val: val.to_string() } };
+        let var = |v: &str| { v.to_string() };
+        let conditions = vec![
+            Condition::True,
+            Condition::Not(Box::new(Condition::False)),
+            check(intro, "yes"),
+            check(twin, "quote \" and \\ and \n"),
+            Condition::And(
+                Box::new(Condition::Or(Box::new(Condition::True), Box::new(Condition::False))),
+                Box::new(Condition::Not(Box::new(
+                    Condition::And(Box::new(Condition::True), Box::new(Condition::True)),
+                ))),
+            ),
+            Condition::Or(
+                Box::new(Condition::True),
+                Box::new(Condition::Or(Box::new(Condition::False), Box::new(Condition::True))),
+            ),
+            Condition::GTE(vec![check(intro, "a"), Condition::True], 1),
+            Condition::LTE(vec![], 0),
+            Condition::Eq { var: var("name"), value: Variable::Str("Mira".to_string()) },
+            Condition::Gt { var: var("gold"), value: Variable::Int(-3) },
+            Condition::Lt { var: var("gold"), value: Variable::Int(i64::MIN) },
+            Condition::Eq { var: var("met"), value: Variable::Bool(true) },
+        ];
+        for condition in conditions {
+            let text = condition.pretty(&names);
+            assert_eq!(Condition::parse(&text, &names), Ok(condition), "{text}");
+        }
+    }
+
+    #[test]
+    fn error_positions() {
+        assert_eq!(position("true && "), 8);
+        assert_eq!(position("true # false"), 5);
+        assert_eq!(position("(true"), 5);
+        assert_eq!(position("true false"), 5);
+        assert_eq!(position("choice(\"nope\") == \"a\""), 7);
+        assert_eq!(position("choice(\"twin\") == \"a\""), 7);
+        assert_eq!(position("var(\"x\") ! 1"), 9);
+        assert_eq!(position("at_least(-1)"), 9);
+        assert_eq!(position("maybe"), 0);
+        assert_eq!(position("var(\"x\") == \"open"), 12);
+        assert_eq!(position("var(\"x\") == \"\\q\""), 14);
+        assert_eq!(position("\u{e9}\u{e9} || #"), 6);
+    }
+}
@@ -0,0 +1,179 @@
+use std::collections::VecDeque;
+
+use super::*;
+
+/// Await steps kept for rollback
+pub const HISTORY_LIMIT: usize = 100;
+
+/// Full state at the oldest kept await step, later ones are replayed from it
+#[derive(Debug, Clone, Default, Hash, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub current: Uuid,
+    pub select_story: BTreeMap<Uuid, String>,
+    pub variables: BTreeMap<String, Variable>,
+    pub call_stack: Vec<Uuid>,
+    pub inspector: Inspector,
+}
+
+/// Visited await step
+#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub current: Uuid,
+    /// Selected option or entered text that moved past the step, `None` for texts
+    #[serde(default)]
+    pub answer: Option<String>,
+}
+
+/// Visited await steps, the last one is current
+#[derive(Debug, Clone, Default, Hash, Serialize, Deserialize)]
+pub struct History {
+    /// State at the first entry
+    base: Option<Snapshot>,
+    entries: VecDeque<HistoryEntry>,
+}
+
+impl AerugoState {
+    fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            current: self.current,
+            select_story: self.select_story.clone(),
+            variables: self.variables.clone(),
+            call_stack: self.call_stack.clone(),
+            inspector: self.inspector.clone(),
+        }
+    }
+
+    fn restore(&mut self, snapshot: Snapshot) {
+        self.current = snapshot.current;
+        self.select_story = snapshot.select_story;
+        self.variables = snapshot.variables;
+        self.call_stack = snapshot.call_stack;
+        self.inspector = snapshot.inspector;
+    }
+
+    /// State at the await step after `entry`
+    fn replay(
+        snapshot: Snapshot,
+        entry: &HistoryEntry,
+        aerugo: &Aerugo,
+    ) -> Result<Snapshot, AerugoError> {
+        let mut state = AerugoState::default();
+        state.restore(snapshot);
+        let step = state.step(aerugo)?;
+        match (&step.inner, &entry.answer) {
+            (Steps::Input { var, .. }, Some(answer)) => {
+                state.set_input(var.clone(), answer.clone());
+            }
+            (_, Some(answer)) => {
+                state.select_unique(step.id, answer.clone());
+            }
+            (_, None) => {}
+        }
+        state.next(aerugo)?;
+        state.collect_with(aerugo, |_| { Ok(()) })?;
+        Ok(state.snapshot())
+    }
+
+    pub(crate) fn remember(&mut self, aerugo: &Aerugo) {
+        if self.history.entries.is_empty() {
+            self.history.base = Some(self.snapshot());
+        } else if self.history.entries.len() >= HISTORY_LIMIT {
+            let first = self.history.entries.pop_front().unwrap();
+            let base = self.history.base.take().unwrap_or_default();
+            match Self::replay(base, &first, aerugo) {
+                Ok(base) => { self.history.base = Some(base); }
+                Err(_) => {
+                    // scenario changed under the history, start over from here
+                    self.history.entries.clear();
+                    self.history.base = Some(self.snapshot());
+                }
+            }
+        }
+        self.history.entries.push_back(HistoryEntry { current: self.current, answer: None });
+    }
+
+    /// Keep the answer given to the current step for replay
+    pub(crate) fn answer(&mut self, aerugo: &Aerugo) {
+        let answer = match aerugo.get(&self.current).map(|step| { &step.inner }) {
+            Some(Steps::Phrase { .. } | Steps::ImageSelect { .. }) => {
+                self.select_story.get(&self.current).cloned()
+            }
+            Some(Steps::Input { var, .. }) => {
+                self.variables.get(var).map(|value| { value.to_string() })
+            }
+            _ => { None }
+        };
+        if let Some(entry) = self.history.entries.back_mut() {
+            if entry.current == self.current {
+                entry.answer = answer;
+            }
+        }
+    }
+
+    /// `key` of `step` was selected on this visit or an earlier one kept in history
+    pub fn was_chosen(&self, step: &Uuid, key: &str) -> bool {
+        self.select_story.get(step).map(String::as_str) == Some(key)
+            || self.history.entries.iter()
+                .any(|entry| { &entry.current == step && entry.answer.as_deref() == Some(key) })
+    }
+
+    /// Go back `count` await steps, returns how many were actually rolled back.
+    /// Use [`AerugoState::reload`] after it to restore graphics
+    pub fn rollback(&mut self, count: usize, aerugo: &Aerugo) -> Result<usize, AerugoError> {
+        // last entry is the current step
+        let available = self.history.entries.len().saturating_sub(1);
+        let count = count.min(available);
+        if count == 0 {
+            return Ok(0);
+        }
+        let kept = self.history.entries.len() - count;
+        let mut snapshot = self.history.base.clone().unwrap_or_default();
+        for entry in self.history.entries.iter().take(kept - 1) {
+            snapshot = Self::replay(snapshot, entry, aerugo)?;
+        }
+        self.history.entries.truncate(kept);
+        if let Some(entry) = self.history.entries.back_mut() {
+            entry.answer = None;
+        }
+        self.restore(snapshot);
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_step as step, test_text as text};
+
+    #[test]
+    fn rollback_replays_answers() {
+        let phrase = step(Steps::Phrase {
+            phrases: vec![PhraseOption::new("a", "A"), PhraseOption::new("b", "B")],
+            timeout: None,
+        });
+        let input = step(Steps::Input {
+            prompt: "".to_string(),
+            var: "name".to_string(),
+            default: "".to_string(),
+            max_len: None,
+        });
+        let mut steps = vec![phrase.clone(), input.clone()];
+        steps.extend((0..HISTORY_LIMIT + 10).map(|_| { step(text("")) }));
+        let aerugo = Aerugo::new(steps);
+
+        let mut runner = Runner::new(&aerugo).unwrap();
+        runner.choose("b").unwrap();
+        runner.input("Mira").unwrap();
+        for _ in 0..HISTORY_LIMIT {
+            runner.proceed().unwrap();
+        }
+        let mut state = runner.into_state();
+        assert!(state.was_chosen(&phrase.id, "b"));
+
+        let expected = state.variables.clone();
+        assert_eq!(state.rollback(HISTORY_LIMIT - 1, &aerugo), Ok(HISTORY_LIMIT - 1));
+        assert_eq!(state.variables, expected);
+        assert_eq!(state.current, aerugo.steps[3].id);
+        assert_eq!(state.rollback(1, &aerugo), Ok(0));
+    }
+}
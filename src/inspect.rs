@@ -1,16 +1,50 @@
 use std::collections::HashMap;
 use super::*;
 
+/// (outfit, expression, position) of shown character
+pub type CharacterLook = (Option<String>, Option<String>, Option<f32>);
 
 /// Keep in mind info about all steps, exclude common
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Inspector {
     /// name: (path, position)
     pub sprites: HashMap<String, (String, f32)>,
+    /// name: transform, missing for default one
+    #[serde(default)]
+    pub transforms: HashMap<String, SpriteTransform>,
+    /// name: look, shown as sprite with the same name
+    #[serde(default)]
+    pub characters: HashMap<String, CharacterLook>,
     /// name: sprite
     pub narrator: HashMap<String, String>,
     pub background: Option<String>,
+    /// Last `SceneCommand::Set` or `SceneCommand::Play`
     pub scene: Option<SceneCommand>,
+    /// Shown frame of playing scene, written by the game on save
+    #[serde(default)]
+    pub scene_frame: Option<usize>,
+    #[serde(default)]
+    pub scene_paused: bool,
+    /// Playing music as `MusicCommand::Play`
+    #[serde(default)]
+    pub music: Option<MusicCommand>,
+    /// Camera (x, y, scale), missing for default one
+    #[serde(default)]
+    pub camera: Option<(f32, f32, f32)>,
+    /// rgba covering screen
+    #[serde(default)]
+    pub fade: Option<[f32; 4]>,
+    #[serde(default)]
+    pub vignette: f32,
+    /// kind: `ParticlesCommand::Start`
+    #[serde(default)]
+    pub particles: BTreeMap<ParticleKind, ParticlesCommand>,
+    /// `TextModeCommand::Nvl` is on
+    #[serde(default)]
+    pub nvl: bool,
+    /// (author, texts) passed on NVL page
+    #[serde(default)]
+    pub page: Vec<(String, String)>,
 }
 
 impl Inspector {
@@ -34,54 +68,206 @@ impl Inspector {
                 Steps::Sprite(cmd) => {
                     match cmd {
                         SpriteCommand::None => {}
-                        SpriteCommand::Set { sprite, name, position } => {
+                        SpriteCommand::Set { sprite, name, position, transform } => {
                             self.sprites.insert(name.clone(), (sprite.clone(), *position));
+                            self.characters.remove(name);
+                            self.keep_transform(name, transform);
                         }
                         SpriteCommand::Remove { name } => {
                             self.sprites.remove(name);
+                            self.transforms.remove(name);
+                            self.characters.remove(name);
                         }
-                        SpriteCommand::FadeIn { sprite, name, position } => {
+                        SpriteCommand::FadeIn { sprite, name, position, transform, .. } => {
                             self.sprites.insert(name.clone(), (sprite.clone(), *position));
+                            self.characters.remove(name);
+                            self.keep_transform(name, transform);
                         }
-                        SpriteCommand::FadeOut { name } => {
+                        SpriteCommand::FadeOut { name, .. } => {
                             self.sprites.remove(name);
+                            self.transforms.remove(name);
+                            self.characters.remove(name);
                         }
-                        SpriteCommand::LeftIn { sprite, name, position } => {
+                        SpriteCommand::LeftIn { sprite, name, position, transform, .. } => {
                             self.sprites.insert(name.clone(), (sprite.clone(), *position));
+                            self.characters.remove(name);
+                            self.keep_transform(name, transform);
                         }
-                        SpriteCommand::LeftOut { name } => {
+                        SpriteCommand::LeftOut { name, .. } => {
                             self.sprites.remove(name);
+                            self.transforms.remove(name);
+                            self.characters.remove(name);
                         }
-                        SpriteCommand::RightIn { sprite, name, position } => {
+                        SpriteCommand::RightIn { sprite, name, position, transform, .. } => {
                             self.sprites.insert(name.clone(), (sprite.clone(), *position));
+                            self.characters.remove(name);
+                            self.keep_transform(name, transform);
                         }
-                        SpriteCommand::RightOut { name } => {
+                        SpriteCommand::RightOut { name, .. } => {
                             self.sprites.remove(name);
+                            self.transforms.remove(name);
+                            self.characters.remove(name);
                         }
-                        SpriteCommand::Move { name, position } => {
+                        SpriteCommand::Move { name, position, .. } => {
                             if let Some((sprite, ..)) = self.sprites.remove(name) {
                                 self.sprites.insert(name.clone(), (sprite, *position));
                             }
+                            if let Some((.., shown_at)) = self.characters.get_mut(name) {
+                                *shown_at = Some(*position);
+                            }
+                        }
+                        SpriteCommand::Transform { name, transform, .. } => {
+                            let shown = self.sprites.contains_key(name)
+                                || self.characters.contains_key(name);
+                            if shown {
+                                self.keep_transform(name, transform);
+                            }
                         }
                     }
                 }
+                Steps::Character(cmd) => {
+                    match cmd {
+                        CharacterCommand::Show {
+                            name, outfit, expression, position, transform, ..
+                        } => {
+                            self.sprites.remove(name);
+                            self.characters.insert(
+                                name.clone(),
+                                (outfit.clone(), expression.clone(), *position),
+                            );
+                            self.keep_transform(name, transform);
+                        }
+                        CharacterCommand::Outfit { name, outfit } => {
+                            if let Some((current, ..)) = self.characters.get_mut(name) {
+                                *current = outfit.clone();
+                            }
+                        }
+                        CharacterCommand::Expression { name, expression } => {
+                            if let Some((_, current, _)) = self.characters.get_mut(name) {
+                                *current = expression.clone();
+                            }
+                        }
+                        CharacterCommand::None => {}
+                    }
+                }
                 Steps::Background(cmd) => {
                     match cmd {
                         BackgroundCommand::Change { new, .. } => {
                             self.background = Some(new.clone());
                         }
                         BackgroundCommand::Shake => {}
+                        BackgroundCommand::ShakeWith { .. } => {}
                         BackgroundCommand::None => {}
                     }
                 }
                 Steps::Scene(cmd) => {
-                    self.scene = Some(cmd.clone());
+                    match cmd {
+                        SceneCommand::Set { .. } | SceneCommand::Play { .. } => {
+                            self.scene = Some(cmd.clone());
+                            self.scene_frame = None;
+                            self.scene_paused = false;
+                        }
+                        SceneCommand::Remove => {
+                            self.scene = None;
+                            self.scene_frame = None;
+                            self.scene_paused = false;
+                        }
+                        SceneCommand::Pause => {
+                            self.scene_paused = true;
+                        }
+                        SceneCommand::Resume => {
+                            self.scene_paused = false;
+                        }
+                        SceneCommand::Stop => {
+                            if let Some(SceneCommand::Play { is_loop, .. }) = &mut self.scene {
+                                *is_loop = false;
+                            }
+                        }
+                        SceneCommand::None => {}
+                    }
+                }
+                Steps::Screen(cmd) => {
+                    match cmd {
+                        ScreenCommand::Zoom { x, y, scale, .. } => {
+                            self.camera = Some((*x, *y, *scale));
+                        }
+                        ScreenCommand::Pan { x, y, .. } => {
+                            let scale = self.camera.map_or(1.0, |(_, _, scale)| { scale });
+                            self.camera = Some((*x, *y, scale));
+                        }
+                        ScreenCommand::Reset { .. } => {
+                            self.camera = None;
+                        }
+                        ScreenCommand::Flash { .. } => {}
+                        ScreenCommand::FadeTo { color, .. } => {
+                            self.fade = Some(*color);
+                        }
+                        ScreenCommand::FadeFrom { .. } => {
+                            self.fade = None;
+                        }
+                        ScreenCommand::Vignette { strength, .. } => {
+                            self.vignette = *strength;
+                        }
+                        ScreenCommand::None => {}
+                    }
+                }
+                Steps::Particles(cmd) => {
+                    match cmd {
+                        ParticlesCommand::Start { kind, .. } => {
+                            self.particles.insert(*kind, cmd.clone());
+                        }
+                        ParticlesCommand::Stop { kind: Some(kind) } => {
+                            self.particles.remove(kind);
+                        }
+                        ParticlesCommand::Stop { kind: None } => {
+                            self.particles.clear();
+                        }
+                        ParticlesCommand::None => {}
+                    }
+                }
+                Steps::TextMode(cmd) => {
+                    match cmd {
+                        TextModeCommand::Adv => {
+                            self.nvl = false;
+                            self.page.clear();
+                        }
+                        TextModeCommand::Nvl => {
+                            self.nvl = true;
+                        }
+                        TextModeCommand::ClearPage => {
+                            self.page.clear();
+                        }
+                        TextModeCommand::None => {}
+                    }
+                }
+                Steps::Music(cmd) => {
+                    match cmd {
+                        MusicCommand::Play { music, is_loop }
+                        | MusicCommand::Crossfade { music, is_loop, .. } => {
+                            self.music = Some(MusicCommand::Play {
+                                music: music.clone(),
+                                is_loop: *is_loop,
+                            });
+                        }
+                        MusicCommand::Stop => {
+                            self.music = None;
+                        }
+                        MusicCommand::None => {}
+                    }
                 }
                 _ => {}
             }
         }
     }
 
+    fn keep_transform(&mut self, name: &String, transform: &SpriteTransform) {
+        if *transform == SpriteTransform::default() {
+            self.transforms.remove(name);
+        } else {
+            self.transforms.insert(name.clone(), transform.clone());
+        }
+    }
+
     pub fn extract(&self) -> Vec<Steps> {
         let mut data: Vec<Steps> = Default::default();
         for (name, (sprite, position)) in &self.sprites {
@@ -89,6 +275,17 @@ impl Inspector {
                 sprite: sprite.clone(),
                 name: name.clone(),
                 position: *position,
+                transform: self.transforms.get(name).cloned().unwrap_or_default(),
+            }));
+        }
+        for (name, (outfit, expression, position)) in &self.characters {
+            data.push(Steps::Character(CharacterCommand::Show {
+                name: name.clone(),
+                outfit: outfit.clone(),
+                expression: expression.clone(),
+                position: *position,
+                transform: self.transforms.get(name).cloned().unwrap_or_default(),
+                duration: None,
             }));
         }
         for (name, sprite) in &self.narrator {
@@ -107,13 +304,56 @@ impl Inspector {
                 Steps::Background(BackgroundCommand::Change {
                     new: sprite.clone(),
                     animation: None,
+                    duration: None,
+                    easing: Easing::Linear,
                 })
             }
         });
         data.push(match &self.scene {
             None => { Steps::Scene(SceneCommand::Remove) }
-            Some(cmd) => { Steps::Scene(cmd.clone()) }
+            Some(cmd) => {
+                let mut cmd = cmd.clone();
+                if let SceneCommand::Play { from, .. } = &mut cmd {
+                    *from = self.scene_frame.or(*from);
+                }
+                Steps::Scene(cmd)
+            }
         });
+        if self.scene_paused {
+            data.push(Steps::Scene(SceneCommand::Pause));
+        }
+        data.push(match &self.music {
+            None => { Steps::Music(MusicCommand::Stop) }
+            Some(cmd) => { Steps::Music(cmd.clone()) }
+        });
+        let (x, y, scale) = self.camera.unwrap_or((0.0, 0.0, 1.0));
+        data.push(Steps::Screen(ScreenCommand::Zoom {
+            x,
+            y,
+            scale,
+            duration: None,
+            easing: Easing::Linear,
+        }));
+        data.push(Steps::Screen(match self.fade {
+            None => { ScreenCommand::FadeFrom { duration: None, easing: Easing::Linear } }
+            Some(color) => {
+                ScreenCommand::FadeTo { color, duration: None, easing: Easing::Linear }
+            }
+        }));
+        data.push(Steps::Screen(ScreenCommand::Vignette {
+            strength: self.vignette,
+            duration: None,
+            easing: Easing::Linear,
+        }));
+        data.push(Steps::Particles(ParticlesCommand::Stop { kind: None }));
+        for cmd in self.particles.values() {
+            data.push(Steps::Particles(cmd.clone()));
+        }
+        // page is read by the game along with the text
+        data.push(Steps::TextMode(match self.nvl {
+            true => { TextModeCommand::Nvl }
+            false => { TextModeCommand::Adv }
+        }));
         data
     }
 }
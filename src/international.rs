@@ -55,7 +55,15 @@ impl Internationale {
         if let Some(localized) = localized {
             for step in aerugo.steps.iter_mut() {
                 if let Some(local) = localized.localized.iter().find(|s| s.id == step.id) {
-                    step.inner = local.inner.clone();
+                    let mut inner = local.inner.clone();
+                    // voice stays with the scenario unless localization has own
+                    if let (
+                        Steps::Text { voice: Some(voice), .. },
+                        Steps::Text { voice: local_voice @ None, .. },
+                    ) = (&step.inner, &mut inner) {
+                        *local_voice = Some(voice.clone());
+                    }
+                    step.inner = inner;
                 } else if let Steps::Text { .. } = step.inner {
                     println!("Missing localized step({}): {:?}", step.id, step.inner);
                 } else if let Steps::Phrase { .. } = step.inner {
@@ -69,7 +77,7 @@ impl Internationale {
 
     pub fn is_relevant(step: &Steps) -> bool {
         match step {
-            Steps::Text { .. } | Steps::Phrase { .. } => { true }
+            Steps::Text { .. } | Steps::Phrase { .. } | Steps::Input { .. } => { true }
             _ => { false }
         }
     }
@@ -1,77 +1,118 @@
 #![allow(dead_code)]
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt::Debug;
 use std::hash::Hasher;
 use bevy::prelude::Resource;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use uuid::Uuid;
 
+pub use audio::*;
+pub use chapters::*;
+pub use character::*;
 pub use condition::*;
+pub use easing::*;
+pub use error::*;
+pub use explore::*;
+pub use expression::*;
+pub use history::*;
+pub use lint::*;
+pub use markup::*;
+pub use particles::*;
+pub use phrase::*;
+pub use runner::*;
+pub use screen::*;
 pub use simple_sprite::*;
+pub use text_mode::*;
+pub use variable::*;
 
 use crate::inspect::Inspector;
 
 mod simple_sprite;
+mod audio;
+mod chapters;
+mod character;
 mod condition;
+mod easing;
+mod error;
+mod explore;
+mod expression;
+mod history;
 mod inspect;
+mod lint;
+mod markup;
+mod particles;
+mod phrase;
+mod runner;
+mod screen;
+mod text_mode;
+mod variable;
 pub mod bevy_glue;
 pub mod international;
 
 #[derive(Debug, Clone, Hash, Serialize, Deserialize, Resource)]
 pub struct AerugoState {
     pub current: Uuid,
-    pub select_story: Vec<(Uuid, String)>,
+    /// Selected phrase option per step
+    #[serde(deserialize_with = "select_story_compat")]
+    pub select_story: BTreeMap<Uuid, String>,
+    #[serde(default)]
+    pub variables: BTreeMap<String, Variable>,
+    /// `Steps::Call` steps awaiting `Steps::Return`
+    #[serde(default)]
+    pub call_stack: Vec<Uuid>,
     pub inspector: Inspector,
+    /// Visited await steps for rollback
+    #[serde(default)]
+    pub history: History,
     _pre_collected: Option<Vec<Steps>>,
 }
 
 impl AerugoState {
-    pub fn new(aerugo: &Aerugo) -> Self {
-        Self {
-            current: aerugo.steps.first().unwrap().id,
-            select_story: vec![],
-            inspector: Default::default(),
-            _pre_collected: None,
-        }
+    pub fn new(aerugo: &Aerugo) -> Result<Self, AerugoError> {
+        let first = aerugo.steps.first().ok_or(AerugoError::EndOfScenario)?;
+        Ok(Self {
+            current: first.id,
+            ..Default::default()
+        })
     }
 
-    fn apply_jump(
-        current: &mut Uuid,
-        select_story: &Vec<(Uuid, String)>,
-        condition: &Option<Condition>,
-        target: &Uuid,
-    ) -> bool
-    {
+    fn apply_jump(&mut self, condition: &Option<Condition>, target: &Uuid) -> bool {
         let condition = match condition {
             None => { true }
-            Some(c) => { c.resolve(&select_story) }
+            Some(c) => { c.resolve(self) }
         };
         if condition {
-            *current = *target;
+            self.current = *target;
         }
         condition
     }
 
-    fn find_next(current: Uuid, aerugo: &Aerugo) -> Uuid {
-        let current_pos = aerugo.steps.iter()
-            .position(|s| { s.id == current })
-            .unwrap();
-        aerugo.steps.get(current_pos + 1).unwrap().id
+    fn apply_var(&mut self, var: &String, operation: &VarOperation) -> Result<(), AerugoError> {
+        let value = operation.apply(self.variables.get(var))
+            .ok_or_else(|| { AerugoError::VariableType(var.clone()) })?;
+        self.variables.insert(var.clone(), value);
+        Ok(())
+    }
+
+    fn find_next(current: Uuid, aerugo: &Aerugo) -> Result<Uuid, AerugoError> {
+        let current_pos = aerugo.position(&current)
+            .ok_or(AerugoError::UnknownStep(current))?;
+        aerugo.steps.get(current_pos + 1)
+            .map(|s| { s.id })
+            .ok_or(AerugoError::EndOfScenario)
     }
 
-    pub fn setup(aerugo: &Aerugo) -> AerugoState {
-        let mut current = aerugo.steps.get(0).unwrap().id;
-        let select_story = Default::default();
+    pub fn setup(aerugo: &Aerugo) -> Result<AerugoState, AerugoError> {
+        let mut state = AerugoState::new(aerugo)?;
         loop {
-            let step = aerugo.steps.iter()
-                .find(|s| { s.id == current })
-                .unwrap();
+            let step = state.step(aerugo)?;
             match &step.inner {
                 Steps::Jump { condition, target } => {
-                    if Self::apply_jump(&mut current, &select_story, condition, target) {
+                    if state.apply_jump(condition, target) {
                         continue;
                     }
                 }
@@ -79,80 +120,164 @@ impl AerugoState {
                 _ => { break; }
             }
 
-            current = Self::find_next(current, &aerugo);
+            state.current = Self::find_next(state.current, aerugo)?;
         }
-        AerugoState { current, select_story, inspector: Default::default(), _pre_collected: None }
+        Ok(state)
     }
 
-    pub fn step(&self, aerugo: &Aerugo) -> Step {
-        aerugo.steps.iter().find(|s| { s.id == self.current }).unwrap().clone()
+    pub fn step(&self, aerugo: &Aerugo) -> Result<Step, AerugoError> {
+        aerugo.get(&self.current)
+            .cloned()
+            .ok_or(AerugoError::UnknownStep(self.current))
     }
 
-    pub fn next(&mut self, aerugo: &Aerugo) -> Option<()> {
-        let current = aerugo.steps
-            .iter()
-            .position(|s| { s.id == self.current })
-            .unwrap();
-        match aerugo.steps.get(current + 1) {
-            None => { None }
-            Some(next) => {
-                self.current = next.id;
-                Some(())
-            }
+    pub fn next(&mut self, aerugo: &Aerugo) -> Result<(), AerugoError> {
+        self.answer(aerugo);
+        // passed text stays on NVL page
+        if let (true, Some(Step { inner: Steps::Text { author, texts, .. }, .. }))
+            = (self.inspector.nvl, aerugo.get(&self.current)) {
+            let shown = (self.interpolate(author), self.interpolate_markup(texts));
+            self.inspector.page.push(shown);
         }
+        self.current = Self::find_next(self.current, aerugo)?;
+        Ok(())
     }
 
     // collect graphic commands steps
-    pub fn collect(&mut self, aerugo: &Aerugo) -> Vec<Steps> {
+    pub fn collect(&mut self, aerugo: &Aerugo) -> Result<Vec<Steps>, AerugoError> {
         if let Some(collected) = self._pre_collected.take() {
-            return collected;
+            return Ok(collected);
         }
+        let steps = self.collect_with(aerugo, |_| { Ok(()) })?;
+        self.remember(aerugo);
+        Ok(steps)
+    }
 
+    /// [`AerugoState::collect`] calling `visit` before each passed step
+    pub(crate) fn collect_with<F>(
+        &mut self,
+        aerugo: &Aerugo,
+        mut visit: F,
+    ) -> Result<Vec<Steps>, AerugoError>
+        where F: FnMut(&AerugoState) -> Result<(), AerugoError>
+    {
         let mut steps: Vec<Steps> = Default::default();
         loop {
-            let step = self.step(&aerugo);
+            visit(self)?;
+            let step = self.step(aerugo)?;
             match &step.inner {
                 // region user await steps
                 Steps::Text { .. }
                 | Steps::Phrase { .. }
-                | Steps::ImageSelect { .. } => { break; }
+                | Steps::ImageSelect { .. }
+                | Steps::Input { .. } => { break; }
                 // endregion
                 Steps::Jump { condition, target } => {
-                    if Self::apply_jump(&mut self.current, &self.select_story, condition, target) {
+                    if self.apply_jump(condition, target) {
                         continue;
                     }
                 }
-                // region graphic commands steps
+                // region subroutines
+                Steps::Call { target } => {
+                    self.call_stack.push(self.current);
+                    self.current = *target;
+                    continue;
+                }
+                Steps::Return => {
+                    // continue right after the call
+                    self.current = self.call_stack.pop()
+                        .ok_or(AerugoError::ReturnWithoutCall(self.current))?;
+                }
+                // endregion
+                // region story variables
+                Steps::SetVar { var, value } => {
+                    self.variables.insert(var.clone(), value.clone());
+                }
+                Steps::ModifyVar { var, operation } => {
+                    self.apply_var(var, operation)?;
+                }
+                // endregion
+                // region graphic and audio commands steps
                 Steps::SpriteNarrator { .. }
                 | Steps::Sprite { .. }
+                | Steps::Character { .. }
                 | Steps::Background { .. }
-                | Steps::Scene { .. } => {
+                | Steps::Scene { .. }
+                | Steps::Screen { .. }
+                | Steps::Particles { .. }
+                | Steps::TextMode { .. }
+                | Steps::Music { .. }
+                | Steps::Sound { .. } => {
                     steps.push(step.inner);
                 }
                 // endregion
                 _ => {}
             }
 
-            self.current = Self::find_next(self.current, &aerugo);
+            self.current = Self::find_next(self.current, aerugo)?;
         }
         self.inspector.keep(&steps);
-        steps
+        Ok(steps)
     }
 
     pub fn select_unique(&mut self, step: Uuid, value: String) {
-        let exist = self.select_story
-            .iter()
-            .rposition(|(s, _)| {
-                *s == step
-            });
-        match exist {
-            None => {
-                self.select_story.push((step, value));
-            }
-            Some(i) => {
-                self.select_story[i] = (step, value);
+        self.select_story.insert(step, value);
+    }
+
+    /// Keep text entered for `Steps::Input`
+    pub fn set_input(&mut self, var: String, value: String) {
+        self.variables.insert(var, Variable::Str(value));
+    }
+
+    /// Put story variables into `{var}` of text, unknown names stay as they are
+    pub fn interpolate(&self, text: &str) -> String {
+        self.interpolate_with(text, |value| { value })
+    }
+
+    /// [`AerugoState::interpolate`] for text with markup, values never turn into tags
+    pub fn interpolate_markup(&self, text: &str) -> String {
+        self.interpolate_with(text, |value| { escape_markup(&value) })
+    }
+
+    fn interpolate_with<F: Fn(String) -> String>(&self, text: &str, shown: F) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('{') {
+            result.push_str(&rest[..start]);
+            rest = &rest[start..];
+            let value = rest.find('}')
+                .and_then(|end| { self.variables.get(&rest[1..end]).map(|var| { (end, var) }) });
+            match value {
+                Some((end, var)) => {
+                    result.push_str(&shown(var.to_string()));
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    result.push('{');
+                    rest = &rest[1..];
+                }
             }
         }
+        result.push_str(rest);
+        result
+    }
+}
+
+/// Older saves keep selections as a list of pairs
+fn select_story_compat<'de, D: Deserializer<'de>>(
+    deserializer: D
+) -> Result<BTreeMap<Uuid, String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum SelectStory {
+        Map(BTreeMap<Uuid, String>),
+        List(Vec<(Uuid, String)>),
+    }
+
+    match SelectStory::deserialize(deserializer)? {
+        SelectStory::Map(map) => { Ok(map) }
+        // later selection of the same step wins
+        SelectStory::List(list) => { Ok(list.into_iter().collect()) }
     }
 }
 
@@ -161,11 +286,11 @@ impl AerugoState {
         ron::to_string(self).unwrap()
     }
 
-    pub fn validate(aerugo: &Aerugo, state: &AerugoState) -> Option<()> {
-        if aerugo.steps.iter().find(|s| { s.id == state.current }).is_none() {
-            return None;
+    pub fn validate(aerugo: &Aerugo, state: &AerugoState) -> Result<(), AerugoError> {
+        if aerugo.position(&state.current).is_none() {
+            return Err(AerugoError::UnknownStep(state.current));
         }
-        Some(())
+        Ok(())
     }
 
     pub fn reload(mut self) -> Self {
@@ -178,8 +303,11 @@ impl Default for AerugoState {
     fn default() -> Self {
         AerugoState {
             current: Default::default(),
-            select_story: vec![],
+            select_story: Default::default(),
+            variables: Default::default(),
+            call_stack: vec![],
             inspector: Default::default(),
+            history: Default::default(),
             _pre_collected: None,
         }
     }
@@ -187,13 +315,81 @@ impl Default for AerugoState {
 
 
 #[derive(Debug, Clone, Hash, Serialize, Deserialize, Resource)]
+#[serde(from = "AerugoSteps")]
 pub struct Aerugo {
     pub steps: Vec<Step>,
+    /// name: definition, for `Steps::Character`
+    #[serde(default)]
+    pub characters: BTreeMap<String, Character>,
+    /// Empty for single file scenario
+    #[serde(skip)]
+    pub chapters: Vec<Chapter>,
+    #[serde(skip)]
+    index: StepIndex,
+}
+
+/// Serialized form of [`Aerugo`], index is rebuilt on load
+#[derive(Deserialize)]
+struct AerugoSteps {
+    steps: Vec<Step>,
+    #[serde(default)]
+    characters: BTreeMap<String, Character>,
+}
+
+impl From<AerugoSteps> for Aerugo {
+    fn from(raw: AerugoSteps) -> Self {
+        let mut aerugo = Aerugo::new(raw.steps);
+        aerugo.characters = raw.characters;
+        aerugo
+    }
+}
+
+/// Step id to position in `Aerugo::steps`
+#[derive(Debug, Clone, Default)]
+struct StepIndex(HashMap<Uuid, usize>);
+
+/// Derived from steps, nothing to hash
+impl std::hash::Hash for StepIndex {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
 }
 
 impl Aerugo {
-    // TODO: Aerugo Errors
-    pub fn validate(&self) -> Result<(), String> {
+    pub fn new(steps: Vec<Step>) -> Aerugo {
+        let mut aerugo = Aerugo {
+            steps,
+            characters: Default::default(),
+            chapters: vec![],
+            index: Default::default(),
+        };
+        aerugo.reindex();
+        aerugo
+    }
+
+    pub fn parse(data: &str) -> Result<Aerugo, AerugoError> {
+        Ok(ron::from_str(data)?)
+    }
+
+    /// Rebuild id index, call after inserting or removing steps
+    pub fn reindex(&mut self) {
+        self.index.0 = self.steps.iter()
+            .enumerate()
+            .map(|(i, s)| { (s.id, i) })
+            .collect();
+    }
+
+    /// Position of step by id, falls back to scan when index is stale
+    pub fn position(&self, id: &Uuid) -> Option<usize> {
+        match self.index.0.get(id) {
+            Some(&i) if self.steps.get(i).map(|s| { &s.id }) == Some(id) => { Some(i) }
+            _ => { self.steps.iter().position(|s| { &s.id == id }) }
+        }
+    }
+
+    pub fn get(&self, id: &Uuid) -> Option<&Step> {
+        self.position(id).map(|i| { &self.steps[i] })
+    }
+
+    pub fn validate(&self) -> Result<(), AerugoError> {
         let mut ids = HashSet::new();
         let mut duplicates = HashSet::new();
         for step in &self.steps {
@@ -204,9 +400,16 @@ impl Aerugo {
             }
         }
         if !duplicates.is_empty() {
-            let duplicates = duplicates.into_iter()
-                .map(|id| id.to_string()).collect::<Vec<_>>();
-            return Err(format!("Found duplicates: {}", duplicates.join(", ")));
+            let mut duplicates = duplicates.into_iter().collect::<Vec<_>>();
+            duplicates.sort();
+            return Err(AerugoError::DuplicateIds(duplicates));
+        }
+        for step in &self.steps {
+            if let Steps::Jump { target, .. } | Steps::Call { target } = &step.inner {
+                if !ids.contains(target) {
+                    return Err(AerugoError::InvalidJumpTarget { step: step.id, target: *target });
+                }
+            }
         }
         Ok(())
     }
@@ -214,9 +417,7 @@ impl Aerugo {
 
 impl Default for Aerugo {
     fn default() -> Self {
-        Aerugo {
-            steps: vec![Step::default()],
-        }
+        Aerugo::new(vec![Step::default()])
     }
 }
 
@@ -225,23 +426,63 @@ pub enum Steps {
     Text {
         author: String,
         texts: String,
+        /// Voice clip played with the text
+        #[serde(default)]
+        voice: Option<String>,
     },
     Jump {
         condition: Option<Condition>,
         target: Uuid,
     },
     Phrase {
-        phrases: Vec<(String, String)>,
+        #[serde(deserialize_with = "phrases_compat")]
+        phrases: Vec<PhraseOption>,
+        /// Select default option when time runs out
+        #[serde(default)]
+        timeout: Option<PhraseTimeout>,
+    },
+    /// Jump to `target` and come back on `Steps::Return`
+    Call {
+        target: Uuid,
     },
+    Return,
     ImageSelect {
         background: String,
         /// (sprite, (x, y))
         options: HashMap<String, (String, (isize, isize))>,
     },
+    /// Text field for player, like the name of main character
+    Input {
+        prompt: String,
+        /// Story variable receiving `Variable::Str`
+        var: String,
+        #[serde(default)]
+        default: String,
+        /// Characters at most, unlimited when missing
+        #[serde(default)]
+        max_len: Option<usize>,
+    },
+    SetVar {
+        var: String,
+        value: Variable,
+    },
+    ModifyVar {
+        var: String,
+        operation: VarOperation,
+    },
     SpriteNarrator(NarratorCommand),
     Sprite(SpriteCommand),
+    Character(CharacterCommand),
     Background(BackgroundCommand),
     Scene(SceneCommand),
+    Screen(ScreenCommand),
+    Particles(ParticlesCommand),
+    TextMode(TextModeCommand),
+    Music(MusicCommand),
+    /// One-shot sound effect
+    Sound {
+        sound: String,
+    },
     None,
 }
 
@@ -251,6 +492,19 @@ impl std::hash::Hash for Steps {
     }
 }
 
+impl Steps {
+    /// Steps where the flow stops and waits for player
+    pub fn is_await(&self) -> bool {
+        matches!(
+            self,
+            Steps::Text { .. }
+                | Steps::Phrase { .. }
+                | Steps::ImageSelect { .. }
+                | Steps::Input { .. }
+        )
+    }
+}
+
 #[derive(Clone, Debug, Hash, Serialize, Deserialize, Resource)]
 pub struct Step {
     pub id: Uuid,
@@ -281,7 +535,7 @@ impl Step {
         Step {
             id: Uuid::new_v4(),
             name: "".to_string(),
-            inner: Steps::Text { author: "".to_string(), texts: "".to_string() },
+            inner: Steps::Text { author: "".to_string(), texts: "".to_string(), voice: None },
         }
     }
 
@@ -326,17 +580,35 @@ impl std::hash::Hash for CommonAnimation {
     }
 }
 
-#[derive(Debug, Clone, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum BackgroundCommand {
     Change {
         new: String,
         animation: Option<CommonAnimation>,
+        /// Seconds of animation, default of the game when missing
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
     },
+    /// Shake with default strength and duration
     Shake,
+    ShakeWith {
+        /// Part of window width
+        strength: f32,
+        /// Seconds
+        duration: f32,
+    },
     None,
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+impl std::hash::Hash for BackgroundCommand {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ron::to_string(self).unwrap().hash(state)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum SceneCommand {
     Set {
         /// sprite
@@ -350,9 +622,114 @@ pub enum SceneCommand {
         tile: (usize, usize),
         columns: usize,
         rows: usize,
+        /// Frames per second, 24 when missing
+        #[serde(default)]
+        fps: Option<f32>,
+        /// Pace of frames over one pass, linear keeps `fps` for every frame
+        #[serde(default)]
+        easing: Easing,
+        /// First and last frame, whole sheet when missing
+        #[serde(default)]
+        frames: Option<(usize, usize)>,
+        /// Play back to the first frame instead of jumping to it
+        #[serde(default)]
+        ping_pong: bool,
+        /// Text waits for the end of animation, ignored with `is_loop`
+        #[serde(default)]
+        wait: bool,
+        /// Frame to start from, kept by [`Inspector`] to resume loaded games
+        #[serde(default)]
+        from: Option<usize>,
     },
     Pause,
     Resume,
     Stop,
     None,
 }
+
+impl std::hash::Hash for SceneCommand {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ron::to_string(self).unwrap().hash(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    /// Step with fresh id, for scenarios of unit tests
+    pub(crate) fn test_step(inner: Steps) -> Step {
+        Step::new().with_inner(inner)
+    }
+
+    pub(crate) fn test_text(texts: &str) -> Steps {
+        Steps::Text { author: "".to_string(), texts: texts.to_string(), voice: None }
+    }
+
+    fn long_scenario() -> (Aerugo, Vec<Uuid>) {
+        let aerugo = Aerugo::new((0..20_000).map(|_| { Step::new() }).collect());
+        let ids = aerugo.steps.iter()
+            .rev()
+            .step_by(10)
+            .map(|s| { s.id })
+            .collect::<Vec<_>>();
+        (aerugo, ids)
+    }
+
+    #[test]
+    fn position_matches_scan() {
+        let (aerugo, ids) = long_scenario();
+        for id in &ids {
+            assert_eq!(aerugo.position(id), aerugo.steps.iter().position(|s| { &s.id == id }));
+        }
+        assert_eq!(aerugo.position(&Uuid::new_v4()), None);
+    }
+
+    /// Timing depends on the machine, run with `cargo test --release -- --ignored`
+    #[test]
+    #[ignore]
+    fn position_indexed_beats_scan() {
+        let (aerugo, ids) = long_scenario();
+
+        let start = Instant::now();
+        let indexed = ids.iter()
+            .map(|id| { aerugo.position(id) })
+            .collect::<Vec<_>>();
+        let indexed_time = start.elapsed();
+
+        let start = Instant::now();
+        let scanned = ids.iter()
+            .map(|id| { aerugo.steps.iter().position(|s| { &s.id == id }) })
+            .collect::<Vec<_>>();
+        let scan_time = start.elapsed();
+
+        assert_eq!(indexed, scanned);
+        assert!(
+            indexed_time < scan_time,
+            "indexed {indexed_time:?} not faster than scan {scan_time:?}",
+        );
+    }
+
+    #[test]
+    fn interpolated_values_stay_plain() {
+        let mut state = AerugoState::default();
+        state.set_input("name".to_string(), "[b]X{p}".to_string());
+        let pieces = parse_markup(&state.interpolate_markup("Hi {name} end"));
+        assert_eq!(pieces.len(), 1);
+        assert_eq!(pieces[0].text, "Hi [b]X{p} end");
+        assert!(pieces[0].markers.is_empty());
+        assert_eq!(state.interpolate("Hi {name}"), "Hi [b]X{p}");
+    }
+
+    #[test]
+    fn position_survives_stale_index() {
+        let mut aerugo = Aerugo::new((0..10).map(|_| { Step::new() }).collect());
+        let id = aerugo.steps[5].id;
+        aerugo.steps.remove(0);
+        assert_eq!(aerugo.position(&id), Some(4));
+        aerugo.reindex();
+        assert_eq!(aerugo.position(&id), Some(4));
+    }
+}
@@ -0,0 +1,352 @@
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+
+use super::*;
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum DiagnosticKind {
+    /// `Steps::Jump` or `Steps::Call` to missing step
+    DanglingJump {
+        target: Uuid,
+    },
+    /// `Condition::Check` of missing step
+    CheckUnknownStep {
+        target: Uuid,
+    },
+    /// `Condition::Check` of step which never records a selection,
+    /// only `Steps::Phrase` and `Steps::ImageSelect` do
+    CheckNotPhrase {
+        target: Uuid,
+    },
+    /// `Condition::Check` of value which no option produces
+    CheckUnknownValue {
+        target: Uuid,
+        val: String,
+    },
+    /// Condition compares story variable which is never set
+    UnknownVariable {
+        var: String,
+    },
+    /// `PhraseTimeout::default` which no option of the step has
+    UnknownTimeoutDefault {
+        val: String,
+    },
+    /// `PhraseTimeout::default` of option with condition, may be not available in time
+    ConditionalTimeoutDefault {
+        val: String,
+    },
+    /// `PhraseTimeout::seconds` which is not positive
+    InvalidTimeout,
+    /// `SceneCommand::Play` with `fps` which is not positive
+    InvalidFps,
+    /// Not reachable from the first step
+    Unreachable,
+    /// Flow from here never reaches an await step
+    DeadEnd,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Diagnostic {
+    pub step: Uuid,
+    pub name: String,
+    pub kind: DiagnosticKind,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({:?}): ", self.step, self.name)?;
+        match &self.kind {
+            DiagnosticKind::DanglingJump { target } => {
+                write!(f, "jump to unknown step {}", target)
+            }
+            DiagnosticKind::CheckUnknownStep { target } => {
+                write!(f, "condition checks unknown step {}", target)
+            }
+            DiagnosticKind::CheckNotPhrase { target } => {
+                write!(f, "condition checks step {} which is not a choice", target)
+            }
+            DiagnosticKind::CheckUnknownValue { target, val } => {
+                write!(f, "condition checks {:?} which step {} never produces", val, target)
+            }
+            DiagnosticKind::UnknownVariable { var } => {
+                write!(f, "condition compares variable {:?} which is never set", var)
+            }
+            DiagnosticKind::UnknownTimeoutDefault { val } => {
+                write!(f, "timeout selects {:?} which is not an option", val)
+            }
+            DiagnosticKind::ConditionalTimeoutDefault { val } => {
+                write!(f, "timeout selects {:?} which has a condition", val)
+            }
+            DiagnosticKind::InvalidTimeout => {
+                write!(f, "timeout is not positive")
+            }
+            DiagnosticKind::InvalidFps => {
+                write!(f, "scene fps is not positive")
+            }
+            DiagnosticKind::Unreachable => {
+                write!(f, "unreachable")
+            }
+            DiagnosticKind::DeadEnd => {
+                write!(f, "never reaches an await step")
+            }
+        }
+    }
+}
+
+impl Aerugo {
+    pub fn lint(&self) -> Vec<Diagnostic> {
+        let variables: HashSet<&String> = self.steps.iter()
+            .filter_map(|s| {
+                match &s.inner {
+                    Steps::SetVar { var, .. }
+                    | Steps::ModifyVar { var, .. }
+                    | Steps::Input { var, .. } => { Some(var) }
+                    _ => { None }
+                }
+            })
+            .collect();
+        let mut diagnostics = Vec::new();
+        let mut report = |step: &Step, kind: DiagnosticKind| {
+            diagnostics.push(Diagnostic { step: step.id, name: step.name.clone(), kind });
+        };
+
+        // region references
+        for step in &self.steps {
+            if let Steps::Call { target } = &step.inner {
+                if self.position(target).is_none() {
+                    report(step, DiagnosticKind::DanglingJump { target: *target });
+                }
+            }
+            if let Steps::Jump { target, .. } = &step.inner {
+                if self.position(target).is_none() {
+                    report(step, DiagnosticKind::DanglingJump { target: *target });
+                }
+            }
+            if let Steps::Phrase { phrases, timeout: Some(timeout) } = &step.inner {
+                match phrases.iter().find(|option| { option.key == timeout.default }) {
+                    None => {
+                        let val = timeout.default.clone();
+                        report(step, DiagnosticKind::UnknownTimeoutDefault { val });
+                    }
+                    Some(option) if option.condition.is_some() => {
+                        let val = timeout.default.clone();
+                        report(step, DiagnosticKind::ConditionalTimeoutDefault { val });
+                    }
+                    Some(_) => {}
+                }
+                if timeout.seconds.is_nan() || timeout.seconds <= 0.0 {
+                    report(step, DiagnosticKind::InvalidTimeout);
+                }
+            }
+            if let Steps::Scene(SceneCommand::Play { fps: Some(fps), .. }) = &step.inner {
+                if fps.is_nan() || *fps <= 0.0 {
+                    report(step, DiagnosticKind::InvalidFps);
+                }
+            }
+            let conditions: Vec<&Condition> = match &step.inner {
+                Steps::Jump { condition, .. } => { condition.iter().collect() }
+                Steps::Phrase { phrases, .. } => {
+                    phrases.iter().filter_map(|option| { option.condition.as_ref() }).collect()
+                }
+                _ => { vec![] }
+            };
+            let mut checks = Vec::new();
+            let mut used = Vec::new();
+            for condition in conditions {
+                condition.checks(&mut checks);
+                condition.variables(&mut used);
+            }
+            for var in used {
+                if !variables.contains(var) {
+                    report(step, DiagnosticKind::UnknownVariable { var: var.clone() });
+                }
+            }
+            for (target, val) in checks {
+                let checked = match self.position(target) {
+                    None => {
+                        report(step, DiagnosticKind::CheckUnknownStep { target: *target });
+                        continue;
+                    }
+                    Some(i) => { &self.steps[i].inner }
+                };
+                let produces = match checked {
+                    Steps::Phrase { phrases, .. } => {
+                        phrases.iter().any(|option| { &option.key == val })
+                    }
+                    Steps::ImageSelect { options, .. } => {
+                        options.contains_key(val)
+                    }
+                    _ => {
+                        report(step, DiagnosticKind::CheckNotPhrase { target: *target });
+                        continue;
+                    }
+                };
+                if !produces {
+                    report(step, DiagnosticKind::CheckUnknownValue {
+                        target: *target,
+                        val: val.clone(),
+                    });
+                }
+            }
+        }
+        // endregion
+
+        // region flow
+        // any return may continue after any call
+        let returns: Vec<usize> = self.steps.iter()
+            .enumerate()
+            .filter(|(_, s)| { matches!(s.inner, Steps::Call { .. }) })
+            .map(|(i, _)| { i + 1 })
+            .filter(|&i| { i < self.steps.len() })
+            .collect();
+        let next = |i: usize| -> Vec<usize> {
+            let mut next = Vec::new();
+            let fallthrough = match &self.steps[i].inner {
+                Steps::Jump { condition, target } => {
+                    if let Some(t) = self.position(target) {
+                        next.push(t);
+                    }
+                    !matches!(condition, None | Some(Condition::True))
+                }
+                Steps::Call { target } => {
+                    if let Some(t) = self.position(target) {
+                        next.push(t);
+                    }
+                    false
+                }
+                Steps::Return => {
+                    next.extend_from_slice(&returns);
+                    false
+                }
+                _ => { true }
+            };
+            if fallthrough && i + 1 < self.steps.len() {
+                next.push(i + 1);
+            }
+            next
+        };
+
+        let mut reachable = vec![false; self.steps.len()];
+        let mut queue = VecDeque::new();
+        if !self.steps.is_empty() {
+            reachable[0] = true;
+            queue.push_back(0);
+        }
+        let mut previous: Vec<Vec<usize>> = vec![Vec::new(); self.steps.len()];
+        for i in 0..self.steps.len() {
+            for n in next(i) {
+                previous[n].push(i);
+            }
+        }
+        while let Some(i) = queue.pop_front() {
+            for n in next(i) {
+                if !reachable[n] {
+                    reachable[n] = true;
+                    queue.push_back(n);
+                }
+            }
+        }
+
+        // walk back from await steps
+        let mut can_await: Vec<bool> = self.steps.iter()
+            .map(|s| { s.inner.is_await() })
+            .collect();
+        let mut queue: VecDeque<usize> = (0..self.steps.len())
+            .filter(|&i| { can_await[i] })
+            .collect();
+        while let Some(i) = queue.pop_front() {
+            for &p in &previous[i] {
+                if !can_await[p] {
+                    can_await[p] = true;
+                    queue.push_back(p);
+                }
+            }
+        }
+
+        for (i, step) in self.steps.iter().enumerate() {
+            if !reachable[i] {
+                report(step, DiagnosticKind::Unreachable);
+                continue;
+            }
+            // only the head of each dead tail, where live flow enters it
+            let head = i == 0 || previous[i].iter().any(|&p| { reachable[p] && can_await[p] });
+            if !can_await[i] && head {
+                report(step, DiagnosticKind::DeadEnd);
+            }
+        }
+        // endregion
+
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_step as step, test_text as text};
+
+    fn kinds(aerugo: &Aerugo) -> Vec<(Uuid, DiagnosticKind)> {
+        aerugo.lint().into_iter().map(|d| { (d.step, d.kind) }).collect()
+    }
+
+    #[test]
+    fn clean_scenario() {
+        let aerugo = Aerugo::new(vec![step(text("a")), step(text("b"))]);
+        assert!(kinds(&aerugo).is_empty());
+    }
+
+    #[test]
+    fn dangling_jump() {
+        let target = Uuid::new_v4();
+        let jump = step(Steps::Jump { condition: None, target });
+        let aerugo = Aerugo::new(vec![step(text("a")), jump.clone()]);
+        assert!(kinds(&aerugo).contains(&(jump.id, DiagnosticKind::DanglingJump { target })));
+    }
+
+    #[test]
+    fn unreachable() {
+        let (skipped, end) = (step(text("skipped")), step(text("end")));
+        let aerugo = Aerugo::new(vec![
+            step(text("a")),
+            step(Steps::Jump { condition: None, target: end.id }),
+            skipped.clone(),
+            end,
+        ]);
+        assert_eq!(kinds(&aerugo), vec![(skipped.id, DiagnosticKind::Unreachable)]);
+    }
+
+    #[test]
+    fn dead_end() {
+        let sound = step(Steps::Sound { sound: "door".to_string() });
+        let aerugo = Aerugo::new(vec![
+            step(text("a")),
+            sound.clone(),
+            step(Steps::Sound { sound: "steps".to_string() }),
+        ]);
+        // only the head of the tail
+        assert_eq!(kinds(&aerugo), vec![(sound.id, DiagnosticKind::DeadEnd)]);
+    }
+
+    #[test]
+    fn check_unknown_value() {
+        let phrase = step(Steps::Phrase {
+            phrases: vec![PhraseOption::new("a", "A"), PhraseOption::new("b", "B")],
+            timeout: None,
+        });
+        let check = |val: &str| {
+            step(Steps::Jump {
+                condition: Some(Condition::Check { step: phrase.id, val: val.to_string() }),
+                target: phrase.id,
+            })
+        };
+        let (known, unknown) = (check("a"), check("c"));
+        let aerugo = Aerugo::new(vec![
+            phrase.clone(),
+            known,
+            unknown.clone(),
+            step(text("end")),
+        ]);
+        let kind = DiagnosticKind::CheckUnknownValue { target: phrase.id, val: "c".to_string() };
+        assert_eq!(kinds(&aerugo), vec![(unknown.id, kind)]);
+    }
+}
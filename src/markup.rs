@@ -0,0 +1,286 @@
+/// Per character animation of [`TextMark`]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum TextEffect {
+    Shake,
+    Wave,
+}
+
+/// Typewriter control inside `Steps::Text`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TextMarker {
+    /// `{w=0.5}`, pause for seconds
+    Wait(f32),
+    /// `{speed=2x}`, multiplier of typing speed up to the next one
+    Speed(f32),
+    /// `{nw}`, go to the next step without click at the end
+    NoWait,
+    /// `{p}`, wait for click and go on in the same box
+    Pause,
+}
+
+/// Look of a piece of `Steps::Text`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextMark {
+    pub bold: bool,
+    pub italic: bool,
+    /// rgba
+    pub color: Option<[f32; 4]>,
+    /// Multiplier of font size
+    pub size: Option<f32>,
+    pub effect: Option<TextEffect>,
+}
+
+/// Text with one look, see [`parse_markup`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextPiece {
+    pub text: String,
+    pub mark: TextMark,
+    /// Annotation of the whole piece, like furigana
+    pub ruby: Option<String>,
+    /// Met before the first character
+    pub markers: Vec<TextMarker>,
+}
+
+/// Split text by markup tags:
+/// `[b]`, `[i]`, `[color=red]` or `[color=#rrggbb]` or `[color=#rrggbbaa]`,
+/// `[size=1.5]`, `[shake]`, `[wave]` and `[ruby=annotation]`, all closed by `[/tag]`.
+/// `[[` is a plain `[`, unknown or broken tags stay in text as is.
+/// Markers of [`TextMarker`] are in braces, `{{` is a plain `{`
+pub fn parse_markup(text: &str) -> Vec<TextPiece> {
+    let mut pieces: Vec<TextPiece> = vec![];
+    let mut current = TextPiece::default();
+    // (tag, mark before it)
+    let mut opened: Vec<(String, TextMark)> = vec![];
+
+    let mut rest = text;
+    while let Some(start) = rest.find(['[', '{']) {
+        current.text.push_str(&rest[..start]);
+        let is_marker = rest[start..].starts_with('{');
+        rest = &rest[start + 1..];
+        if is_marker {
+            if let Some(after) = rest.strip_prefix('{') {
+                current.text.push('{');
+                rest = after;
+                continue;
+            }
+            let marker = rest.find('}')
+                .and_then(|end| { parse_marker(&rest[..end]).map(|marker| { (end, marker) }) });
+            match marker {
+                Some((end, marker)) => {
+                    if !current.text.is_empty() {
+                        let (mark, ruby) = (current.mark.clone(), current.ruby.clone());
+                        next_piece(&mut pieces, &mut current, mark, ruby);
+                    }
+                    current.markers.push(marker);
+                    rest = &rest[end + 1..];
+                }
+                None => { current.text.push('{'); }
+            }
+            continue;
+        }
+        if let Some(after) = rest.strip_prefix('[') {
+            current.text.push('[');
+            rest = after;
+            continue;
+        }
+        let tag = match rest.find(']') {
+            Some(end) => { &rest[..end] }
+            None => {
+                current.text.push('[');
+                continue;
+            }
+        };
+
+        let mut mark = current.mark.clone();
+        let mut ruby = current.ruby.clone();
+        let known = match tag.strip_prefix('/') {
+            Some(name) => {
+                match opened.iter().rposition(|(opened, _)| { opened == name }) {
+                    Some(i) => {
+                        mark = opened[i].1.clone();
+                        // inner tags close together with outer one
+                        if opened[i..].iter().any(|(opened, _)| { opened == "ruby" }) {
+                            ruby = None;
+                        }
+                        opened.truncate(i);
+                        true
+                    }
+                    None => { false }
+                }
+            }
+            None => {
+                let (name, value) = tag.split_once('=').unwrap_or((tag, ""));
+                let known = match (name, value) {
+                    ("b", "") => { mark.bold = true; true }
+                    ("i", "") => { mark.italic = true; true }
+                    ("shake", "") => { mark.effect = Some(TextEffect::Shake); true }
+                    ("wave", "") => { mark.effect = Some(TextEffect::Wave); true }
+                    ("color", value) => {
+                        mark.color = parse_color(value);
+                        mark.color.is_some()
+                    }
+                    ("size", value) => {
+                        mark.size = value.parse().ok().filter(|size: &f32| { *size > 0.0 });
+                        mark.size.is_some()
+                    }
+                    ("ruby", value) if !value.is_empty() && ruby.is_none() => {
+                        ruby = Some(value.to_string());
+                        true
+                    }
+                    _ => { false }
+                };
+                if known {
+                    opened.push((name.to_string(), current.mark.clone()));
+                }
+                known
+            }
+        };
+        if !known {
+            current.text.push('[');
+            continue;
+        }
+
+        rest = &rest[tag.len() + 1..];
+        if mark != current.mark || ruby != current.ruby {
+            next_piece(&mut pieces, &mut current, mark, ruby);
+        }
+    }
+    current.text.push_str(rest);
+    if !current.text.is_empty() || !current.markers.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+/// Finish `current` piece, markers of an empty one move to the next
+fn next_piece(
+    pieces: &mut Vec<TextPiece>,
+    current: &mut TextPiece,
+    mark: TextMark,
+    ruby: Option<String>,
+)
+{
+    let mut next = TextPiece { text: String::new(), mark, ruby, markers: vec![] };
+    if current.text.is_empty() {
+        next.markers = std::mem::take(&mut current.markers);
+    }
+    let done = std::mem::replace(current, next);
+    if !done.text.is_empty() {
+        pieces.push(done);
+    }
+}
+
+fn parse_marker(marker: &str) -> Option<TextMarker> {
+    let (name, value) = marker.split_once('=').unwrap_or((marker, ""));
+    match (name, value) {
+        ("nw", "") => { Some(TextMarker::NoWait) }
+        ("p", "") => { Some(TextMarker::Pause) }
+        ("w", value) => {
+            value.parse().ok()
+                .filter(|seconds: &f32| { *seconds >= 0.0 })
+                .map(TextMarker::Wait)
+        }
+        ("speed", value) => {
+            value.strip_suffix('x').unwrap_or(value).parse().ok()
+                .filter(|speed: &f32| { *speed > 0.0 })
+                .map(TextMarker::Speed)
+        }
+        _ => { None }
+    }
+}
+
+/// Text shown as is by [`parse_markup`]
+pub fn escape_markup(text: &str) -> String {
+    text.replace('[', "[[").replace('{', "{{")
+}
+
+/// Text as player reads it, without markup and annotations
+pub fn plain_text(text: &str) -> String {
+    parse_markup(text).into_iter()
+        .map(|piece| { piece.text })
+        .collect()
+}
+
+fn parse_color(value: &str) -> Option<[f32; 4]> {
+    let named = match value {
+        "white" => { Some([1.0, 1.0, 1.0, 1.0]) }
+        "black" => { Some([0.0, 0.0, 0.0, 1.0]) }
+        "gray" => { Some([0.5, 0.5, 0.5, 1.0]) }
+        "red" => { Some([0.8, 0.1, 0.1, 1.0]) }
+        "green" => { Some([0.1, 0.6, 0.1, 1.0]) }
+        "blue" => { Some([0.1, 0.2, 0.8, 1.0]) }
+        "yellow" => { Some([0.9, 0.8, 0.1, 1.0]) }
+        _ => { None }
+    };
+    if named.is_some() {
+        return named;
+    }
+    let hex = value.strip_prefix('#')?;
+    if !(hex.len() == 6 || hex.len() == 8) || !hex.is_ascii() {
+        return None;
+    }
+    let mut color = [1.0; 4];
+    for (i, channel) in color.iter_mut().enumerate().take(hex.len() / 2) {
+        let byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+        *channel = byte as f32 / 255.0;
+    }
+    Some(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(pieces: &[TextPiece]) -> Vec<&str> {
+        pieces.iter().map(|piece| { piece.text.as_str() }).collect()
+    }
+
+    #[test]
+    fn nested_tags_close_out_of_order() {
+        let pieces = parse_markup("[b]bold [i]both[/b] tail[/i] end");
+        assert_eq!(texts(&pieces), vec!["bold ", "both", " tail[/i] end"]);
+        assert!(pieces[0].mark.bold && !pieces[0].mark.italic);
+        assert!(pieces[1].mark.bold && pieces[1].mark.italic);
+        // closing outer tag closes the inner one too
+        assert_eq!(pieces[2].mark, TextMark::default());
+    }
+
+    #[test]
+    fn escapes_stay_plain() {
+        let pieces = parse_markup("[[b]] {{p}");
+        assert_eq!(texts(&pieces), vec!["[b]] {p}"]);
+        assert!(pieces[0].markers.is_empty());
+        assert_eq!(parse_markup(&escape_markup("[i]{nw}")), parse_markup("[[i]{{nw}"));
+    }
+
+    #[test]
+    fn unknown_and_broken_tags_stay_literal() {
+        let broken = ["[x]a[/x]", "[b", "a[/b]", "[color=nope]a", "[size=-1]a", "{name}", "{w=}", "{p"];
+        for text in broken {
+            let pieces = parse_markup(text);
+            assert_eq!(texts(&pieces), vec![text]);
+            assert_eq!(pieces[0].mark, TextMark::default());
+            assert!(pieces[0].markers.is_empty());
+        }
+    }
+
+    #[test]
+    fn markers_at_both_ends() {
+        let pieces = parse_markup("{w=0.5}{speed=2x}Hi{p}{nw}");
+        assert_eq!(texts(&pieces), vec!["Hi", ""]);
+        assert_eq!(pieces[0].markers, vec![TextMarker::Wait(0.5), TextMarker::Speed(2.0)]);
+        assert_eq!(pieces[1].markers, vec![TextMarker::Pause, TextMarker::NoWait]);
+        assert_eq!(parse_markup("{speed=3}")[0].markers, vec![TextMarker::Speed(3.0)]);
+    }
+
+    #[test]
+    fn hex_colors() {
+        let pieces = parse_markup("[color=#ff000080]a[/color][color=#00FF00]b[/color]c");
+        assert_eq!(texts(&pieces), vec!["a", "b", "c"]);
+        assert_eq!(pieces[0].mark.color, Some([1.0, 0.0, 0.0, 128.0 / 255.0]));
+        assert_eq!(pieces[1].mark.color, Some([0.0, 1.0, 0.0, 1.0]));
+        assert_eq!(pieces[2].mark.color, None);
+        assert_eq!(parse_color("#12345"), None);
+        assert_eq!(parse_color("#gg0000"), None);
+    }
+}
@@ -0,0 +1,40 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum ParticleKind {
+    #[default]
+    Rain,
+    Snow,
+    Dust,
+    Petals,
+}
+
+/// Weather over the background and under the sprites, one emitter for every kind
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum ParticlesCommand {
+    /// Start emitter of `kind` or change running one
+    Start {
+        kind: ParticleKind,
+        /// 1 is the usual amount
+        intensity: f32,
+        /// Horizontal drift, negative blows to the left
+        #[serde(default)]
+        wind: f32,
+        /// Image of particle, colored rectangle when missing
+        #[serde(default)]
+        sprite: Option<String>,
+    },
+    /// Stop emitting, flying particles fall out, all kinds when `kind` is missing
+    Stop {
+        #[serde(default)]
+        kind: Option<ParticleKind>,
+    },
+    #[default]
+    None,
+}
+
+impl std::hash::Hash for ParticlesCommand {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ron::to_string(self).unwrap().hash(state)
+    }
+}
@@ -0,0 +1,90 @@
+use serde::de::{self, MapAccess, SeqAccess, Visitor};
+
+use super::*;
+
+/// Option of `Steps::Phrase`
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct PhraseOption {
+    /// Selected value, see [`AerugoState::select_unique`]
+    pub key: String,
+    pub verbose: String,
+    /// Option is offered while true, always when missing
+    #[serde(default)]
+    pub condition: Option<Condition>,
+    /// Reason shown on greyed option when `condition` is false, hidden without it
+    #[serde(default)]
+    pub disabled: Option<String>,
+}
+
+impl PhraseOption {
+    pub fn new(key: &str, verbose: &str) -> Self {
+        PhraseOption {
+            key: key.to_string(),
+            verbose: verbose.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Player can select it now
+    pub fn is_available(&self, state: &AerugoState) -> bool {
+        self.condition.as_ref().is_none_or(|condition| { condition.resolve(state) })
+    }
+
+    /// Player sees it now, maybe greyed
+    pub fn is_visible(&self, state: &AerugoState) -> bool {
+        self.disabled.is_some() || self.is_available(state)
+    }
+}
+
+/// Countdown of `Steps::Phrase`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhraseTimeout {
+    pub seconds: f32,
+    /// Key of option selected when time runs out
+    pub default: String,
+}
+
+impl std::hash::Hash for PhraseTimeout {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ron::to_string(self).unwrap().hash(state)
+    }
+}
+
+/// Older scenarios keep options as (key, verbose) pairs
+pub(crate) fn phrases_compat<'de, D: Deserializer<'de>>(
+    deserializer: D
+) -> Result<Vec<PhraseOption>, D::Error> {
+    // untagged enum can not read `Condition` from ron, so pick by shape
+    struct PhraseCompat(PhraseOption);
+
+    impl<'de> Deserialize<'de> for PhraseCompat {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(PhraseVisitor).map(PhraseCompat)
+        }
+    }
+
+    struct PhraseVisitor;
+
+    impl<'de> Visitor<'de> for PhraseVisitor {
+        type Value = PhraseOption;
+
+        fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+            write!(f, "phrase option or (key, verbose) pair")
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let key: String = seq.next_element()?
+                .ok_or_else(|| { de::Error::invalid_length(0, &self) })?;
+            let verbose: String = seq.next_element()?
+                .ok_or_else(|| { de::Error::invalid_length(1, &self) })?;
+            Ok(PhraseOption::new(&key, &verbose))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+            PhraseOption::deserialize(de::value::MapAccessDeserializer::new(map))
+        }
+    }
+
+    let phrases = Vec::<PhraseCompat>::deserialize(deserializer)?;
+    Ok(phrases.into_iter().map(|phrase| { phrase.0 }).collect())
+}
@@ -0,0 +1,177 @@
+use super::*;
+
+/// Drives [`AerugoState`] through a scenario without Bevy, like the game systems do
+#[derive(Debug, Clone)]
+pub struct Runner<'a> {
+    aerugo: &'a Aerugo,
+    state: AerugoState,
+    /// Graphic commands collected before the current await step
+    commands: Vec<Steps>,
+}
+
+impl<'a> Runner<'a> {
+    pub fn new(aerugo: &'a Aerugo) -> Result<Self, AerugoError> {
+        Self::from_state(aerugo, AerugoState::setup(aerugo)?)
+    }
+
+    /// Continue from loaded state
+    pub fn from_state(aerugo: &'a Aerugo, mut state: AerugoState) -> Result<Self, AerugoError> {
+        let commands = state.collect(aerugo)?;
+        Ok(Self { aerugo, state, commands })
+    }
+
+    /// Current await step: `Steps::Text`, `Steps::Phrase`, `Steps::ImageSelect` or `Steps::Input`
+    pub fn current(&self) -> Result<Step, AerugoError> {
+        self.state.step(self.aerugo)
+    }
+
+    pub fn commands(&self) -> &[Steps] {
+        &self.commands
+    }
+
+    pub fn state(&self) -> &AerugoState {
+        &self.state
+    }
+
+    pub fn into_state(self) -> AerugoState {
+        self.state
+    }
+
+    /// Move past `Steps::Text`, choice steps require [`Runner::choose`]
+    /// and `Steps::Input` requires [`Runner::input`]
+    pub fn proceed(&mut self) -> Result<&[Steps], AerugoError> {
+        let step = self.current()?;
+        if let Steps::Phrase { .. } | Steps::ImageSelect { .. } | Steps::Input { .. } = step.inner {
+            return Err(AerugoError::ChoiceRequired(step.id));
+        }
+        self.advance()
+    }
+
+    /// Select option by key of `Steps::Phrase` or `Steps::ImageSelect`
+    pub fn choose(&mut self, value: &str) -> Result<&[Steps], AerugoError> {
+        let step = self.current()?;
+        let exists = match &step.inner {
+            Steps::Phrase { phrases, .. } => {
+                phrases.iter()
+                    .any(|option| { option.key == value && option.is_available(&self.state) })
+            }
+            Steps::ImageSelect { options, .. } => {
+                options.contains_key(value)
+            }
+            _ => { false }
+        };
+        if !exists {
+            return Err(AerugoError::InvalidChoice { step: step.id, value: value.to_string() });
+        }
+        self.state.select_unique(step.id, value.to_string());
+        self.advance()
+    }
+
+    /// Enter text into `Steps::Input`, cut to its `max_len`
+    pub fn input(&mut self, value: &str) -> Result<&[Steps], AerugoError> {
+        let step = self.current()?;
+        let (var, max_len) = match &step.inner {
+            Steps::Input { var, max_len, .. } => { (var.clone(), *max_len) }
+            _ => {
+                return Err(AerugoError::InvalidChoice { step: step.id, value: value.to_string() });
+            }
+        };
+        let value = value.chars().take(max_len.unwrap_or(usize::MAX)).collect();
+        self.state.set_input(var, value);
+        self.advance()
+    }
+
+    fn advance(&mut self) -> Result<&[Steps], AerugoError> {
+        self.state.next(self.aerugo)?;
+        self.commands = self.state.collect(self.aerugo)?;
+        Ok(&self.commands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{test_step as step, test_text as text};
+
+    fn texts_of(runner: &Runner) -> String {
+        match runner.current().unwrap().inner {
+            Steps::Text { texts, .. } => { texts }
+            inner => { panic!("not a text: {inner:?}") }
+        }
+    }
+
+    #[test]
+    fn proceed_collects_commands() {
+        let aerugo = Aerugo::new(vec![
+            step(text("first")),
+            step(Steps::Sound { sound: "door".to_string() }),
+            step(text("second")),
+        ]);
+        let mut runner = Runner::new(&aerugo).unwrap();
+        assert_eq!(texts_of(&runner), "first");
+        assert!(runner.commands().is_empty());
+
+        let commands = runner.proceed().unwrap();
+        assert!(matches!(commands, [Steps::Sound { sound }] if sound == "door"));
+        assert_eq!(texts_of(&runner), "second");
+        assert_eq!(runner.proceed().err(), Some(AerugoError::EndOfScenario));
+    }
+
+    #[test]
+    fn choose_follows_jump() {
+        let phrase = step(Steps::Phrase {
+            phrases: vec![PhraseOption::new("left", "Left"), PhraseOption::new("right", "Right")],
+            timeout: None,
+        });
+        let right = step(text("right"));
+        let aerugo = Aerugo::new(vec![
+            phrase.clone(),
+            step(Steps::Jump {
+                condition: Some(Condition::Check { step: phrase.id, val: "right".to_string() }),
+                target: right.id,
+            }),
+            step(text("left")),
+            right,
+        ]);
+
+        let mut runner = Runner::new(&aerugo).unwrap();
+        assert_eq!(runner.proceed().err(), Some(AerugoError::ChoiceRequired(phrase.id)));
+        assert_eq!(
+            runner.choose("up").err(),
+            Some(AerugoError::InvalidChoice { step: phrase.id, value: "up".to_string() }),
+        );
+        runner.choose("right").unwrap();
+        assert_eq!(texts_of(&runner), "right");
+
+        let mut runner = Runner::new(&aerugo).unwrap();
+        runner.choose("left").unwrap();
+        assert_eq!(texts_of(&runner), "left");
+    }
+
+    #[test]
+    fn input_is_cut_to_max_len() {
+        let aerugo = Aerugo::new(vec![
+            step(Steps::Input {
+                prompt: "".to_string(),
+                var: "name".to_string(),
+                default: "".to_string(),
+                max_len: Some(4),
+            }),
+            step(text("{name}")),
+        ]);
+        let mut runner = Runner::new(&aerugo).unwrap();
+        assert!(runner.proceed().is_err());
+        runner.input("Miranda").unwrap();
+        assert_eq!(runner.state().interpolate(&texts_of(&runner)), "Mira");
+    }
+
+    #[test]
+    fn from_state_resumes() {
+        let aerugo = Aerugo::new(vec![step(text("first")), step(text("second"))]);
+        let mut runner = Runner::new(&aerugo).unwrap();
+        runner.proceed().unwrap();
+
+        let runner = Runner::from_state(&aerugo, runner.into_state()).unwrap();
+        assert_eq!(texts_of(&runner), "second");
+    }
+}
@@ -0,0 +1,77 @@
+use super::*;
+
+/// Effects over the whole screen, text box stays untouched.
+/// Positions are fractions of window from its center, durations are seconds, at once when missing
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum ScreenCommand {
+    /// Zoom camera into region centered at (x, y), `scale` above 1 zooms in
+    Zoom {
+        x: f32,
+        y: f32,
+        scale: f32,
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
+    },
+    /// Move camera center to (x, y) keeping zoom
+    Pan {
+        x: f32,
+        y: f32,
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
+    },
+    /// Drop zoom and pan
+    Reset {
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
+    },
+    /// Short flash fading out from `color`
+    Flash {
+        /// rgba
+        color: [f32; 4],
+        duration: f32,
+    },
+    /// Cover screen with `color`, its alpha is the final opacity
+    FadeTo {
+        /// rgba
+        color: [f32; 4],
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
+    },
+    /// Uncover screen faded by `ScreenCommand::FadeTo`
+    FadeFrom {
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
+    },
+    /// Darken screen edges, 0 removes vignette and 1 is the darkest one
+    Vignette {
+        strength: f32,
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
+    },
+    #[default]
+    None,
+}
+
+impl std::hash::Hash for ScreenCommand {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ron::to_string(self).unwrap().hash(state)
+    }
+}
+
+impl ScreenCommand {
+    pub const WHITE: [f32; 4] = [1.0, 1.0, 1.0, 1.0];
+    pub const RED: [f32; 4] = [1.0, 0.0, 0.0, 0.8];
+    pub const BLACK: [f32; 4] = [0.0, 0.0, 0.0, 1.0];
+}
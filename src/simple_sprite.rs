@@ -3,6 +3,34 @@ use super::*;
 /// Bounds [`f32::NEG_INFINITY`] or [`f32::INFINITY`] or in range [-1; 1]
 type Position = f32;
 
+/// Everything about sprite placement except horizontal position
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpriteTransform {
+    /// In range [-1; 1] from bottom to top
+    pub y: f32,
+    /// 1.0 is window size
+    pub scale: f32,
+    /// Mirror horizontally
+    pub flip: bool,
+    /// Higher layers are drawn over lower ones
+    pub layer: i8,
+    /// rgba multiplied with sprite, darken with values below 1.0
+    pub tint: [f32; 4],
+}
+
+impl Default for SpriteTransform {
+    fn default() -> Self {
+        SpriteTransform {
+            y: 0.0,
+            scale: 1.0,
+            flip: false,
+            layer: 0,
+            tint: [1.0, 1.0, 1.0, 1.0],
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum SpriteCommand {
     None,
@@ -10,6 +38,8 @@ pub enum SpriteCommand {
         sprite: String,
         name: String,
         position: Position,
+        #[serde(default)]
+        transform: SpriteTransform,
     },
     Remove {
         name: String,
@@ -18,29 +48,73 @@ pub enum SpriteCommand {
         sprite: String,
         name: String,
         position: Position,
+        #[serde(default)]
+        transform: SpriteTransform,
+        /// Seconds, default of the game when missing
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
     },
     FadeOut {
         name: String,
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
     },
     LeftIn {
         sprite: String,
         name: String,
         position: Position,
+        #[serde(default)]
+        transform: SpriteTransform,
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
     },
     LeftOut {
         name: String,
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
     },
     RightIn {
         sprite: String,
         name: String,
         position: Position,
+        #[serde(default)]
+        transform: SpriteTransform,
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
     },
     RightOut {
         name: String,
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
     },
     Move {
         name: String,
         position: Position,
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
+    },
+    /// Change transform of shown sprite, layer and flip at once
+    Transform {
+        name: String,
+        transform: SpriteTransform,
+        #[serde(default)]
+        duration: Option<f32>,
+        #[serde(default)]
+        easing: Easing,
     },
 }
 
@@ -0,0 +1,14 @@
+use super::*;
+
+/// How `Steps::Text` is shown
+#[derive(Debug, Clone, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum TextModeCommand {
+    /// One text at a time in the text box at the bottom, drops NVL page
+    Adv,
+    /// Texts pile up on a full-screen panel until `TextModeCommand::ClearPage`
+    Nvl,
+    /// Start a new NVL page
+    ClearPage,
+    #[default]
+    None,
+}
@@ -0,0 +1,76 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Variable {
+    Int(i64),
+    Bool(bool),
+    Str(String),
+}
+
+impl Default for Variable {
+    fn default() -> Self {
+        Variable::Int(0)
+    }
+}
+
+/// As interpolated into text
+impl Display for Variable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Variable::Int(v) => { write!(f, "{}", v) }
+            Variable::Bool(v) => { write!(f, "{}", v) }
+            Variable::Str(v) => { write!(f, "{}", v) }
+        }
+    }
+}
+
+/// Only variables of the same type are comparable
+impl PartialOrd for Variable {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Variable::Int(l), Variable::Int(r)) => { l.partial_cmp(r) }
+            (Variable::Bool(l), Variable::Bool(r)) => { l.partial_cmp(r) }
+            (Variable::Str(l), Variable::Str(r)) => { l.partial_cmp(r) }
+            _ => { None }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum VarOperation {
+    Add(i64),
+    Sub(i64),
+    Toggle,
+    Append(String),
+}
+
+impl Default for VarOperation {
+    fn default() -> Self {
+        VarOperation::Add(1)
+    }
+}
+
+impl VarOperation {
+    /// Missing variable counts as empty value of operation type,
+    /// `None` on type mismatch. Integers stick to their bounds instead of overflow
+    pub fn apply(&self, var: Option<&Variable>) -> Option<Variable> {
+        match (self, var) {
+            (VarOperation::Add(n), None) => { Some(Variable::Int(*n)) }
+            (VarOperation::Add(n), Some(Variable::Int(v))) => {
+                Some(Variable::Int(v.saturating_add(*n)))
+            }
+            (VarOperation::Sub(n), None) => { Some(Variable::Int(0i64.saturating_sub(*n))) }
+            (VarOperation::Sub(n), Some(Variable::Int(v))) => {
+                Some(Variable::Int(v.saturating_sub(*n)))
+            }
+            (VarOperation::Toggle, None) => { Some(Variable::Bool(true)) }
+            (VarOperation::Toggle, Some(Variable::Bool(v))) => { Some(Variable::Bool(!v)) }
+            (VarOperation::Append(s), None) => { Some(Variable::Str(s.clone())) }
+            (VarOperation::Append(s), Some(Variable::Str(v))) => { Some(Variable::Str(format!("{}{}", v, s))) }
+            _ => { None }
+        }
+    }
+}